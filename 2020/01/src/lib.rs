@@ -0,0 +1,192 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    diff::{Rng, Variant},
+    parse::Parser,
+    Answer, Config, Solution, SolveError,
+};
+use tracing::debug;
+
+type StarsType = u32;
+const YEAR: StarsType = 2020;
+
+const EXAMPLE: &str = r"1721
+    979
+    366
+    299
+    675
+    1456";
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let (no1, no2) = find_year(input, config.param_or("year", YEAR)?);
+        Ok(Answer::from(no1 * no2))
+    }
+
+    fn part2(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let summands = find_year_vec(input, 3, config.param_or("year", YEAR)?);
+        Ok(Answer::from(summands.iter().product::<StarsType>()))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let expenses = Parser::new(input).each_line(|line| line.number::<StarsType>())?;
+        Ok(serde_json::json!(expenses))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input
+            .lines()
+            .all(|line| line.trim().parse::<StarsType>().is_ok())
+    }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![Variant::new(1, "find_year_vec", part1_vec)]
+    }
+
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        let mut expenses = (0..rng.range(2, 40))
+            .map(|_| rng.range(1, YEAR as u64) as StarsType)
+            .collect::<Vec<_>>();
+        // make sure there is a pair
+        let no1 = rng.range(1, YEAR as u64) as StarsType;
+        let idx = rng.index(expenses.len());
+        expenses[idx] = no1;
+        expenses.insert(rng.index(expenses.len() + 1), YEAR - no1);
+
+        let lines = expenses.iter().map(|expense| expense.to_string());
+        Some(lines.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+fn part1_vec(input: &str, config: &Config) -> Result<Answer, SolveError> {
+    let summands = find_year_vec(input, 2, config.param_or("year", YEAR)?);
+    Ok(Answer::from(summands.iter().product::<StarsType>()))
+}
+
+#[inline(always)]
+fn find_year(input: &str, year: StarsType) -> (StarsType, StarsType) {
+    let expenses: Vec<StarsType> = input
+        .lines()
+        .map(|line| line.trim().parse::<StarsType>().unwrap())
+        .collect();
+    for expense in &expenses {
+        for find in &expenses {
+            if find + expense == year {
+                debug!(find, expense, "found entries");
+                return (*find, *expense);
+            }
+        }
+    }
+    panic!("not found {}", year);
+}
+
+#[inline(always)]
+fn find_year_vec(input: &str, number: usize, year: StarsType) -> Vec<StarsType> {
+    let expenses: Vec<StarsType> = input
+        .lines()
+        .map(|line| line.trim().parse::<StarsType>().unwrap())
+        .collect();
+
+    fn f(
+        summands: &mut Vec<StarsType>,
+        vals: &Vec<StarsType>,
+        number: usize,
+        depth: usize,
+        year: StarsType,
+    ) -> bool {
+        if summands.len() == depth {
+            summands.push(0);
+        }
+
+        for val in vals {
+            {
+                summands[depth] = *val;
+            }
+            if depth + 1 == number {
+                if summands.iter().sum::<StarsType>() == year {
+                    return true;
+                } else {
+                    continue;
+                }
+            } else if f(summands, vals, number, depth + 1, year) {
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut summands = Vec::new();
+    f(&mut summands, &expenses, number, 0, year);
+
+    summands
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{diff, input::stored_input};
+
+    use super::*;
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, 1).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_find_2020() {
+        let (no1, no2) = find_year(EXAMPLE, YEAR);
+        assert_eq!(no1 + no2, YEAR);
+    }
+
+    #[test]
+    fn test_multiply_2020_vals() {
+        let (no1, no2) = find_year(EXAMPLE, YEAR);
+        assert_eq!(no1 * no2, 514579);
+    }
+
+    #[test]
+    fn test_multiply_2020_vec() {
+        let summands = find_year_vec(EXAMPLE, 2, YEAR);
+        assert_eq!(summands.iter().product::<StarsType>(), 514579);
+        let summands = find_year_vec(EXAMPLE, 3, YEAR);
+        assert_eq!(summands.iter().product::<StarsType>(), 241861950);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, 1);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 988771);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 171933104);
+    }
+
+    #[test]
+    fn test_variants() {
+        let config = Config::new(2020, 1);
+        match diff::check(&Day, &config, 200, 0) {
+            Ok(summary) => assert_eq!(summary.inputs, 201),
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
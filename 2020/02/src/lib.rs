@@ -0,0 +1,148 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    parse::{ParseError, Parser},
+    Answer, Config, Solution, SolveError,
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(parse(input)?))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(parse2(input)?))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let policies = read_policies(input)?
+            .iter()
+            .map(|policy| {
+                serde_json::json!({
+                    "min": policy.min,
+                    "max": policy.max,
+                    "letter": policy.letter,
+                    "password": policy.password,
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!(policies))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| {
+            let line = line.trim();
+            line.contains(": ") && line.contains('-')
+        })
+    }
+}
+
+/// `1-3 a: abcde`
+struct Policy<'a> {
+    min: usize,
+    max: usize,
+    letter: char,
+    password: &'a str,
+}
+
+fn read_policies(input: &str) -> Result<Vec<Policy<'_>>, ParseError> {
+    Parser::new(input).each_line(|line| {
+        let min = line.number()?;
+        if min == 0 {
+            return Err(line.error("positions start at 1"));
+        }
+        line.tag("-")?;
+        let at_max = *line;
+        let max = line.number()?;
+        if max < min {
+            return Err(at_max.error(format!("{} is below the minimum {}", max, min)));
+        }
+        let letter = line.char()?;
+        line.tag(":")?;
+        let password = line.take_rest();
+        Ok(Policy {
+            min,
+            max,
+            letter,
+            password,
+        })
+    })
+}
+
+fn parse(input: &str) -> Result<usize, ParseError> {
+    Ok(read_policies(input)?
+        .iter()
+        .filter(|policy| {
+            let count = policy.password.matches(policy.letter).count();
+            (policy.min..=policy.max).contains(&count)
+        })
+        .count())
+}
+
+fn parse2(input: &str) -> Result<usize, ParseError> {
+    Ok(read_policies(input)?
+        .iter()
+        .filter(|policy| {
+            let is_letter =
+                |pos: usize| policy.password.chars().nth(pos - 1) == Some(policy.letter);
+            is_letter(policy.min) != is_letter(policy.max)
+        })
+        .count())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"1-3 a: abcde
+    1-3 b: cdefg
+    2-9 c: ccccccccc";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, 2).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(TEST_INPUT), Ok(2));
+    }
+
+    #[test]
+    fn test_parse2() {
+        assert_eq!(parse2(TEST_INPUT), Ok(1));
+        assert_eq!(
+            parse2("1-3 a: abcde\n3-1 b: cdefg")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: 1 is below the minimum 3"
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, 2);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 582);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 729);
+    }
+}
@@ -0,0 +1,213 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    geometry::{Point, Vector},
+    repl::{self, Command},
+    Answer, Config, Solution, SolveError,
+};
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let input_vec = input_vec(input);
+        Ok(Answer::from(tree_encounter_count(
+            &input_vec,
+            Vector::new(3, 1),
+        )))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let input_vec = input_vec(input);
+        let tree_count_prod = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .map(|&(right, down)| tree_encounter_count(&input_vec, Vector::new(right, down)))
+            .product::<usize>();
+
+        Ok(Answer::from(tree_count_prod))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input
+            .lines()
+            .all(|line| line.trim().chars().all(|c| c == '.' || c == '#'))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        Ok(serde_json::json!(input_vec(input)))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![Command::new(
+            "tree_encounter_count",
+            "<right> <down>",
+            "trees on the slope",
+            |input, args, _config| {
+                let right = repl::arg(args, 0, "right")?;
+                let down = repl::arg(args, 1, "down")?;
+                // the slope would never reach the bottom
+                if down < 1 {
+                    return Err("<down> must be at least 1".to_string());
+                }
+                let slope = Vector::new(right, down);
+                Ok(tree_encounter_count(&input_vec(input), slope).to_string())
+            },
+        )]
+    }
+}
+
+#[inline(always)]
+fn input_vec(input: &str) -> Vec<&str> {
+    input.lines().map(|line| line.trim()).collect()
+}
+
+/// The point on the map, which repeats to the right, the bottom row ends a
+/// slope
+fn position(input_vec: &[&str], pos: Point) -> Point {
+    let width = input_vec[0].len() as i64;
+    let height = input_vec.len() as i64;
+    Point::new(pos.x.rem_euclid(width), pos.y.min(height))
+}
+
+#[inline(always)]
+fn is_tree(input_vec: &[&str], pos: Point) -> bool {
+    input_vec[pos.y as usize].chars().nth(pos.x as usize) == Some('#')
+}
+
+fn tree_encounter_count(input_vec: &[&str], slope: Vector) -> usize {
+    let height = input_vec.len() as i64;
+    let mut pos = Point::ORIGIN;
+    let mut tree_count = 0;
+
+    loop {
+        pos = position(input_vec, pos + slope);
+        if pos.y >= height {
+            break;
+        }
+        if is_tree(input_vec, pos) {
+            tree_count += 1;
+        }
+    }
+
+    tree_count
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"..##.......
+    #...#...#..
+    .#....#..#.
+    ..#.#...#.#
+    .#...##..#.
+    ..#.##.....
+    .#.#.#....#
+    .#........#
+    #.##...#...
+    #...##....#
+    .#..#...#.#";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, 3).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_input_vec() {
+        let input_vec = input_vec(TEST_INPUT);
+        assert_eq!(input_vec.len(), 11);
+        assert_eq!(input_vec[0].len(), 11);
+    }
+
+    #[test]
+    fn test_position() {
+        let input_vec = input_vec(TEST_INPUT);
+        let pos = position(&input_vec, Point::new(0, 0));
+        assert_eq!(pos, Point::new(0, 0));
+        let pos = position(&input_vec, Point::new(1, 0));
+        assert_eq!(pos, Point::new(1, 0));
+        let pos = position(&input_vec, Point::new(5, 3));
+        assert_eq!(pos, Point::new(5, 3));
+        let pos = position(&input_vec, Point::new(10, 0));
+        assert_eq!(pos, Point::new(10, 0));
+        let pos = position(&input_vec, Point::new(0, 10));
+        assert_eq!(pos, Point::new(0, 10));
+        let pos = position(&input_vec, Point::new(10, 10));
+        assert_eq!(pos, Point::new(10, 10));
+        let pos = position(&input_vec, Point::new(11, 10));
+        assert_eq!(pos, Point::new(0, 10));
+        let pos = position(&input_vec, Point::new(15, 0));
+        assert_eq!(pos, Point::new(4, 0));
+        let pos = position(&input_vec, Point::new(39, 0));
+        assert_eq!(pos, Point::new(6, 0));
+        let pos = position(&input_vec, Point::new(25, 12));
+        assert_eq!(pos, Point::new(3, 11));
+    }
+
+    #[test]
+    fn test_is_tree() {
+        let input_vec = input_vec(TEST_INPUT);
+        assert!(!is_tree(&input_vec, Point::new(1, 0)));
+        assert!(is_tree(&input_vec, Point::new(2, 0)));
+        assert!(is_tree(&input_vec, Point::new(3, 0)));
+        assert!(is_tree(&input_vec, Point::new(1, 10)));
+        assert!(!is_tree(&input_vec, Point::new(2, 10)));
+    }
+
+    #[test]
+    fn test_tree_encounter_count() {
+        let input_vec = input_vec(TEST_INPUT);
+        let tree_count = tree_encounter_count(&input_vec, Vector::new(3, 1));
+        assert_eq!(tree_count, 7);
+    }
+
+    #[test]
+    fn test_tree_encounter_count2() {
+        let input_vec = input_vec(TEST_INPUT);
+        let tree_count1 = tree_encounter_count(&input_vec, Vector::new(1, 1));
+        assert_eq!(tree_count1, 2);
+
+        let tree_count2 = tree_encounter_count(&input_vec, Vector::new(3, 1));
+        assert_eq!(tree_count2, 7);
+
+        let tree_count3 = tree_encounter_count(&input_vec, Vector::new(5, 1));
+        assert_eq!(tree_count3, 3);
+
+        let tree_count4 = tree_encounter_count(&input_vec, Vector::new(7, 1));
+        assert_eq!(tree_count4, 4);
+
+        let tree_count5 = tree_encounter_count(&input_vec, Vector::new(1, 2));
+        assert_eq!(tree_count5, 2);
+
+        assert_eq!(
+            tree_count1 * tree_count2 * tree_count3 * tree_count4 * tree_count5,
+            336
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, 3);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 200);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 3737923200u64);
+    }
+}
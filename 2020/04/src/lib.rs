@@ -0,0 +1,309 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use std::collections::BTreeMap;
+
+use aoc_common::{
+    bits::{Flag, Flags},
+    parse::{ParseError, Parser},
+    Answer, Config, Solution, SolveError,
+};
+
+const PASSPORT_FIELDS: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+const EYE_COLOR: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// The keys of `PASSPORT_FIELDS`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Byr,
+    Iyr,
+    Eyr,
+    Hgt,
+    Hcl,
+    Ecl,
+    Pid,
+    Cid,
+}
+
+impl Flag for Field {
+    const ALL: &'static [Self] = &[
+        Field::Byr,
+        Field::Iyr,
+        Field::Eyr,
+        Field::Hgt,
+        Field::Hcl,
+        Field::Ecl,
+        Field::Pid,
+        Field::Cid,
+    ];
+
+    fn bit(self) -> usize {
+        self as usize
+    }
+}
+
+impl Field {
+    fn from_key(key: &str) -> Option<Self> {
+        let pos = PASSPORT_FIELDS.iter().position(|&x| x == key)?;
+        Some(Field::ALL[pos])
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_valid_passports(input, false)?))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_valid_passports(input, true)?))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let passports = Parser::new(input)
+            .records()
+            .map(|passport| {
+                Ok(passport
+                    .fields(':')?
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>())
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(serde_json::json!(passports))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input
+            .split_whitespace()
+            .all(|field| field.split_once(':').is_some())
+    }
+}
+
+fn count_valid_passports(input: &str, is_part2: bool) -> Result<usize, ParseError> {
+    // cid is optional
+    let required = Flags::all().without(Field::Cid);
+    let mut count = 0;
+    for passport in Parser::new(input).records() {
+        if parse_passport(passport, is_part2)?.is_superset(&required) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// The present, and in part 2 valid, fields
+#[inline(always)]
+fn parse_passport(passport: Parser, is_part2: bool) -> Result<Flags<Field>, ParseError> {
+    let mut check_fields = Flags::empty();
+
+    for (key, value) in passport.fields(':')? {
+        if let Some(field) = Field::from_key(key) {
+            if !is_part2 || is_field_valid(key, value) {
+                check_fields.insert(field);
+            }
+        }
+    }
+
+    Ok(check_fields)
+}
+
+#[inline(always)]
+fn is_field_valid(field: &str, value: &str) -> bool {
+    match field {
+        "byr" => {
+            let value = value.parse::<u16>().unwrap_or_default();
+            (1920..=2002).contains(&value)
+        }
+        "iyr" => {
+            let value = value.parse::<u16>().unwrap_or_default();
+            (2010..=2020).contains(&value)
+        }
+        "eyr" => {
+            let value = value.parse::<u16>().unwrap_or_default();
+            (2020..=2030).contains(&value)
+        }
+        "hgt" => {
+            if let Some(cm) = value.strip_suffix("cm") {
+                let value = cm.parse::<u16>().unwrap_or_default();
+                (150..=193).contains(&value)
+            } else if let Some(inch) = value.strip_suffix("in") {
+                let value = inch.parse::<u16>().unwrap_or_default();
+                (59..=76).contains(&value)
+            } else {
+                false
+            }
+        }
+        "hcl" => {
+            if let Some(color) = value.strip_prefix("#") {
+                color.len() == 6
+                    && color
+                        .find(|c| c < '0' || (c > '9' && c < 'a') || c > 'f')
+                        .is_none()
+            } else {
+                false
+            }
+        }
+        "ecl" => EYE_COLOR.contains(&value),
+        "pid" => value.matches(char::is_numeric).count() == 9,
+        "cid" => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    // ["byr=0 =>0x01", "iyr=1 =>0x02", "eyr=2 =>0x04", "hgt=3 =>0x08",
+    //  "hcl=4 =>0x10", "ecl=5 =>0x20", "pid=6 =>0x40", "cid=7 =>0x80"]
+    const TEST_INPUT: &str = r" ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+                                byr:1937 iyr:2017 cid:147 hgt:183cm
+                                
+                                iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+                                hcl:#cfa07d byr:1929
+                                
+                                hcl:#ae17e1 iyr:2013
+                                eyr:2024
+                                ecl:brn pid:760753108 byr:1931
+                                hgt:179cm
+                                
+                                hcl:#cfa07d eyr:2025 pid:166559648
+                                iyr:2011 ecl:brn hgt:59in";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, 4).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_count_valid_passports() {
+        assert_eq!(count_valid_passports(TEST_INPUT, false), Ok(2));
+    }
+
+    #[test]
+    fn test_parse_passport() {
+        let check_fields = Parser::new(TEST_INPUT)
+            .records()
+            .map(|passport| parse_passport(passport, false).map(|fields| fields.bits()))
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(check_fields, Ok(vec![0xff, 0xf7, 0x7f, 0x7e]));
+
+        let err = count_valid_passports("ecl:gry pid:860033327\nbyr:1937 iyr2017", false);
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "line 2, column 10: expected 'key:value', found 'iyr2017'"
+        );
+    }
+
+    #[test]
+    fn test_is_field_valid() {
+        assert!(is_field_valid("byr", "1920"));
+        assert!(is_field_valid("byr", "2002"));
+        assert!(!is_field_valid("byr", "1919"));
+        assert!(!is_field_valid("byr", "2003"));
+
+        assert!(is_field_valid("iyr", "2010"));
+        assert!(is_field_valid("iyr", "2020"));
+        assert!(!is_field_valid("iyr", "2009"));
+        assert!(!is_field_valid("iyr", "2021"));
+
+        assert!(is_field_valid("eyr", "2020"));
+        assert!(is_field_valid("eyr", "2030"));
+        assert!(!is_field_valid("eyr", "2019"));
+        assert!(!is_field_valid("eyr", "2031"));
+
+        assert!(is_field_valid("hgt", "150cm"));
+        assert!(is_field_valid("hgt", "193cm"));
+        assert!(!is_field_valid("hgt", "149cm"));
+        assert!(!is_field_valid("hgt", "194cm"));
+        assert!(!is_field_valid("hgt", "170"));
+
+        assert!(is_field_valid("hgt", "59in"));
+        assert!(is_field_valid("hgt", "76in"));
+        assert!(!is_field_valid("hgt", "58in"));
+        assert!(!is_field_valid("hgt", "77in"));
+        assert!(!is_field_valid("hgt", "60"));
+
+        assert!(is_field_valid("hcl", "#ffffff"));
+        assert!(is_field_valid("hcl", "#000000"));
+        assert!(is_field_valid("hcl", "#0a9d1f"));
+        assert!(!is_field_valid("hcl", "000000"));
+        assert!(!is_field_valid("hcl", "ffffff"));
+        assert!(!is_field_valid("hcl", "#000"));
+        assert!(!is_field_valid("hcl", "#fffffv"));
+
+        for ecl in EYE_COLOR {
+            assert!(is_field_valid("ecl", ecl));
+        }
+        assert!(!is_field_valid("ecl", "err"));
+
+        assert!(is_field_valid("pid", "023456789"));
+        assert!(!is_field_valid("pid", "12345678"));
+        assert!(!is_field_valid("pid", "12345678a"));
+        assert!(!is_field_valid("pid", "0123456789"));
+    }
+
+    #[test]
+    fn test_invalid_passports() {
+        const TEST_INVALID_PASSPORTS: &str = r"
+                eyr:1972 cid:100
+                hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+                
+                iyr:2019
+                hcl:#602927 eyr:1967 hgt:170cm
+                ecl:grn pid:012533040 byr:1946
+                
+                hcl:dab227 iyr:2012
+                ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+                
+                hgt:59cm ecl:zzz
+                eyr:2038 hcl:74454a iyr:2023
+                pid:3556412378 byr:2007";
+
+        assert_eq!(count_valid_passports(TEST_INVALID_PASSPORTS, true), Ok(0));
+    }
+
+    #[test]
+    fn test_valid_passports() {
+        const TEST_VALID_PASSPORTS: &str = r"
+                pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+                hcl:#623a2f
+                
+                eyr:2029 ecl:blu cid:129 byr:1989
+                iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+                
+                hcl:#888785
+                hgt:164cm byr:2001 iyr:2015 cid:88
+                pid:545766238 ecl:hzl
+                eyr:2022
+                
+                iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+        assert_eq!(count_valid_passports(TEST_VALID_PASSPORTS, true), Ok(4));
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, 4);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 256);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 198);
+    }
+}
@@ -0,0 +1,150 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{Answer, Config, Solution, SolveError};
+
+type Row = u8;
+type Col = u8;
+type Seat = u16;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let seats = sorted_seats(input);
+        Ok(Answer::from(seats[0]))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let seats = sorted_seats(input);
+        seats
+            .windows(2)
+            .find(|pair| pair[0] - pair[1] > 1)
+            .map(|pair| Answer::from(pair[0] - 1))
+            .ok_or_else(|| SolveError::NoSolution("no free seat".to_string()))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let passes = input
+            .lines()
+            .map(|line| {
+                let (row, col, seat) = parse_boarding_nr(line.trim());
+                serde_json::json!({ "row": row, "col": col, "seat": seat })
+            })
+            .collect::<Vec<_>>();
+        Ok(serde_json::Value::from(passes))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| {
+            let line = line.trim();
+            line.len() == 10 && line.chars().all(|c| "FBLR".contains(c))
+        })
+    }
+}
+
+/// Seat ids from highest to lowest
+fn sorted_seats(input: &str) -> Vec<Seat> {
+    let mut seats = input
+        .lines()
+        .map(|line| {
+            let bnr = parse_boarding_nr(line.trim());
+            bnr.2 as Seat
+        })
+        .collect::<Vec<Seat>>();
+    seats.sort_unstable();
+    seats.reverse();
+    seats
+}
+
+fn parse_boarding_nr(value: &str) -> (Row, Col, Seat) {
+    let mut row_start = 0u8;
+    let mut row_end = 127u8;
+    let mut col_start = 0u8;
+    let mut col_end = 7u8;
+    let mut row = &row_end;
+    let mut col = &col_end;
+
+    let chars = value.chars().collect::<Vec<char>>();
+
+    for c in &chars[0..7] {
+        match c {
+            'F' => {
+                row_end = (row_end - row_start - 1) / 2 + row_start;
+                row = &row_end;
+            }
+            'B' => {
+                row_start = (row_end - row_start).div_ceil(2) + row_start;
+                row = &row_start;
+            }
+            _ => {}
+        }
+    }
+
+    for c in &chars[7..10] {
+        match c {
+            'L' => {
+                col_end = (col_end - col_start - 1) / 2 + col_start;
+                col = &col_end;
+            }
+            'R' => {
+                col_start = (col_end - col_start).div_ceil(2) + col_start;
+                col = &col_start;
+            }
+            _ => {}
+        }
+    }
+
+    (*row, *col, *row as u16 * 8 + *col as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    // const TEST_INPUT: &str = r"";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, 5).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_parse_boarding_nr() {
+        let bnr = "BFFFBBFRRR";
+        let parsed = parse_boarding_nr(bnr);
+        assert_eq!(parsed, (70, 7, 567));
+
+        let bnr = "FFFBBBFRRR";
+        let parsed = parse_boarding_nr(bnr);
+        assert_eq!(parsed, (14, 7, 119));
+
+        let bnr = "BBFFBBFRLL";
+        let parsed = parse_boarding_nr(bnr);
+        assert_eq!(parsed, (102, 4, 820));
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, 5);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 835);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 649);
+    }
+}
@@ -0,0 +1,51 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{Answer, Config, SolveError, Solution};
+
+// set to the puzzle day after copying the template
+const DAY: u8 = 0;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(input.lines().count()))
+    }
+
+    fn part2(&self, _input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2020, DAY).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2020, DAY);
+        assert_eq!(Day.part1(TEST_INPUT, &config).unwrap(), 0);
+    }
+}
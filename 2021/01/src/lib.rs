@@ -0,0 +1,120 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{parse::Parser, Answer, Config, Solution, SolveError};
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_increased(input)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_increased_window(input)))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let depths = Parser::new(input).each_line(|line| line.number::<u16>())?;
+        Ok(serde_json::json!(depths))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| line.trim().parse::<u32>().is_ok())
+    }
+}
+
+fn count_increased(input: &str) -> usize {
+    let mut last_val_opt = None;
+    input
+        .lines()
+        .filter(|line| {
+            if let Some(last_val) = last_val_opt {
+                let val = line.trim().parse::<u16>().unwrap();
+                let res = val > last_val;
+                last_val_opt.replace(val);
+                res
+            } else {
+                last_val_opt.replace(line.trim().parse::<u16>().unwrap());
+                false
+            }
+        })
+        .count()
+}
+
+fn count_increased_window(input: &str) -> usize {
+    let mut last_vals = Vec::new();
+    input
+        .lines()
+        .filter(|line| {
+            if last_vals.len() == 3 {
+                let val = line.trim().parse::<u16>().unwrap();
+                let sum_old = last_vals.iter().sum::<u16>();
+                let sum = sum_old - last_vals.remove(0) + val;
+                last_vals.push(val);
+
+                sum_old < sum
+            } else {
+                last_vals.push(line.trim().parse::<u16>().unwrap());
+                false
+            }
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"199
+    200
+    208
+    210
+    200
+    207
+    240
+    269
+    260
+    263";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 1).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_count_increased() {
+        let count = count_increased(TEST_INPUT);
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn test_count_increased_window() {
+        let count = count_increased_window(TEST_INPUT);
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 1);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 1288);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 1311);
+    }
+}
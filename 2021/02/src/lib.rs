@@ -0,0 +1,163 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{parse::Parser, Answer, Config, Solution, SolveError};
+
+const COMMANDS: &[&str] = &["forward", "down", "up"];
+
+struct Position {
+    pub horizontal: u16,
+    pub depth: u16,
+    pub aim: u32,
+    pub aim_depth: u32,
+}
+
+impl Position {
+    fn new() -> Self {
+        Self {
+            horizontal: 0,
+            depth: 0,
+            aim: 0,
+            aim_depth: 0,
+        }
+    }
+
+    fn forward(&mut self, n: u16) {
+        self.horizontal += n;
+        self.aim_depth += self.aim.checked_mul(n as u32).unwrap();
+    }
+
+    fn down(&mut self, n: u16) {
+        self.depth += n;
+        self.aim += n as u32;
+    }
+
+    fn up(&mut self, n: u16) {
+        self.depth -= n;
+        self.aim -= n as u32;
+    }
+
+    fn result(&self) -> u32 {
+        (self.horizontal as u32)
+            .checked_mul(self.depth as u32)
+            .unwrap()
+    }
+
+    fn result_aimed(&self) -> u32 {
+        (self.horizontal as u32)
+            .checked_mul(self.aim_depth)
+            .unwrap()
+    }
+}
+
+fn control(pos: &mut Position, input: &str) {
+    input.lines().for_each(|line| {
+        let mut iter = line.split_whitespace();
+        let cmd = iter.next().unwrap();
+        let n = iter.next().unwrap().parse::<u16>().unwrap();
+        match cmd {
+            "forward" => pos.forward(n),
+            "down" => pos.down(n),
+            "up" => pos.up(n),
+            _ => {}
+        }
+    });
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let mut pos = Position::new();
+        control(&mut pos, input);
+        Ok(Answer::from(pos.result()))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let mut pos = Position::new();
+        control(&mut pos, input);
+        Ok(Answer::from(pos.result_aimed()))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let commands = Parser::new(input).each_line(|line| {
+            let at_cmd = *line;
+            let cmd = line.word()?;
+            if !COMMANDS.contains(&cmd) {
+                return Err(at_cmd.error(format!("unknown command '{}'", cmd)));
+            }
+            Ok((cmd, line.number::<u16>()?))
+        })?;
+        Ok(serde_json::json!(commands))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| {
+            matches!(
+                line.split_whitespace().next(),
+                Some(cmd) if COMMANDS.contains(&cmd)
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"forward 5
+    down 5
+    forward 8
+    up 3
+    down 8
+    forward 2";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 2).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_control_result() {
+        let mut pos = Position::new();
+
+        control(&mut pos, TEST_INPUT);
+        let res = pos.result();
+
+        assert_eq!(res, 150);
+    }
+
+    #[test]
+    fn test_control_result_aimed() {
+        let mut pos = Position::new();
+
+        control(&mut pos, TEST_INPUT);
+        let res = pos.result_aimed();
+
+        assert_eq!(res, 900);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 2);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 1604850);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 1685186100);
+    }
+}
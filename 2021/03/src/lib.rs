@@ -0,0 +1,301 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use std::fmt;
+
+use aoc_common::{bits, repl::Command, Answer, Config, Solution, SolveError};
+
+type BinType = u64;
+
+#[derive(Clone, Copy)]
+struct Bin {
+    data: BinType,
+    data_len: usize,
+}
+
+impl fmt::Debug for Bin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0width$b}", self.data, width = self.data_len)
+    }
+}
+
+impl From<Bin> for BinType {
+    fn from(val: Bin) -> Self {
+        val.data
+    }
+}
+
+impl From<&Bin> for BinType {
+    fn from(val: &Bin) -> Self {
+        val.data
+    }
+}
+
+impl From<&str> for Bin {
+    fn from(s: &str) -> Self {
+        let mut data: BinType = 0;
+        let mut pos = s.len();
+        s.chars().for_each(|c| {
+            pos -= 1;
+
+            match c {
+                '0' => data |= 0 << pos,
+                '1' => data |= 1 << pos,
+                _ => {}
+            };
+        });
+
+        Self {
+            data,
+            data_len: s.len(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BinContainer {
+    data: Vec<Bin>,
+    data_len: usize,
+}
+
+impl BinContainer {
+    fn new(input: &str) -> Self {
+        let mut data = Vec::new();
+        input
+            .lines()
+            .for_each(|line| data.push(Bin::from(line.trim())));
+        let data_len = if data.is_empty() { 0 } else { data[0].data_len };
+        Self { data, data_len }
+    }
+
+    /// Numbers with bit `i` set, per bit
+    fn count_ones(&self, data: &[&Bin]) -> Vec<usize> {
+        bits::column_counts(data.iter().map(|&b| BinType::from(b)), self.data_len)
+    }
+
+    /// Bits set where `pick(zeros, ones)` holds
+    fn rate(&self, pick: impl Fn(usize, usize) -> bool) -> BinType {
+        let data = self.data.iter().collect::<Vec<_>>();
+        let ones = self.count_ones(&data);
+
+        // works with fixed size data
+        (0..self.data_len)
+            .filter(|&i| pick(data.len() - ones[i], ones[i]))
+            .fold(0, |res, i| res | 1 << i)
+    }
+
+    fn gamma(&self) -> BinType {
+        // most common values wins
+        self.rate(|zeros, ones| zeros < ones)
+    }
+
+    fn epsilon(&self) -> BinType {
+        // least common values win
+        self.rate(|zeros, ones| zeros > ones)
+    }
+
+    fn power_consumption(&self) -> u64 {
+        self.epsilon().checked_mul(self.gamma()).unwrap()
+    }
+
+    /// The last number left after keeping the zeros at every bit from the
+    /// top where `keep_zeros(zeros, ones)` holds, else the ones
+    fn rating(&self, keep_zeros: impl Fn(usize, usize) -> bool) -> BinType {
+        let mut data: Vec<&Bin> = self.data.iter().collect();
+
+        // works with fixed size data
+        for i in (0..self.data_len).rev() {
+            let ones = self.count_ones(&data)[i];
+            let keep = match keep_zeros(data.len() - ones, ones) {
+                true => 0,
+                false => 1,
+            };
+            data.retain(|&b| (BinType::from(b) >> i) & 1 == keep);
+            if data.len() == 1 {
+                break;
+            }
+        }
+
+        data[0].into()
+    }
+
+    fn o2(&self) -> BinType {
+        // most common values wins
+        self.rating(|zeros, ones| zeros > ones)
+    }
+
+    fn co2(&self) -> BinType {
+        // least common values wins
+        self.rating(|zeros, ones| zeros <= ones)
+    }
+
+    fn life_support_rating(&self) -> u64 {
+        self.o2().checked_mul(self.co2()).unwrap()
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(BinContainer::new(input).power_consumption()))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(BinContainer::new(input).life_support_rating()))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input
+            .lines()
+            .all(|line| line.trim().chars().all(|c| c == '0' || c == '1'))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let bins = BinContainer::new(input);
+        let numbers = bins.data.iter().map(BinType::from).collect::<Vec<_>>();
+        Ok(serde_json::json!({ "width": bins.data_len, "numbers": numbers }))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![
+            Command::new(
+                "bins",
+                "",
+                "parsed binary numbers",
+                |input, _args, _config| Ok(format!("{:#?}", BinContainer::new(input))),
+            ),
+            Command::new(
+                "rates",
+                "",
+                "gamma, epsilon, O2 and CO2 ratings",
+                |input, _args, _config| {
+                    let bins = BinContainer::new(input);
+                    let width = bins.data_len;
+                    Ok([
+                        ("gamma", bins.gamma()),
+                        ("epsilon", bins.epsilon()),
+                        ("o2", bins.o2()),
+                        ("co2", bins.co2()),
+                    ]
+                    .iter()
+                    .map(|(name, rate)| format!("{:<8}{:0width$b} {}", name, rate, rate))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"00100
+    11110
+    10110
+    10111
+    10101
+    01111
+    00111
+    11100
+    10000
+    11001
+    00010
+    01010";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 3).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_bin() {
+        let s = "00001";
+        let b = Bin::from(s);
+        let n: BinType = b.into();
+        assert_eq!(n, 1);
+
+        let s = "00010";
+        let b = Bin::from(s);
+        let n: BinType = b.into();
+        assert_eq!(n, 2);
+
+        let s = "00011";
+        let b = Bin::from(s);
+        let n: BinType = b.into();
+        assert_eq!(n, 3);
+
+        let s = "10011";
+        let b = &Bin::from(s);
+        let n: BinType = b.into();
+        assert_eq!(n, 19);
+    }
+
+    #[test]
+    fn test_gamma() {
+        let container = BinContainer::new(TEST_INPUT);
+        let g = container.gamma();
+        assert_eq!(g, 22);
+    }
+
+    #[test]
+    fn test_epsilon() {
+        let container = BinContainer::new(TEST_INPUT);
+        let g = container.epsilon();
+        assert_eq!(g, 9);
+    }
+
+    #[test]
+    fn test_power_consumption() {
+        let container = BinContainer::new(TEST_INPUT);
+        let res = container.power_consumption();
+        assert_eq!(res, 198);
+    }
+
+    #[test]
+    fn test_o2() {
+        let container = BinContainer::new(TEST_INPUT);
+        let o2 = container.o2();
+        assert_eq!(o2, 23)
+    }
+
+    #[test]
+    fn test_co2() {
+        let container = BinContainer::new(TEST_INPUT);
+        let co2 = container.co2();
+        assert_eq!(co2, 10)
+    }
+
+    #[test]
+    fn test_life_support_rating() {
+        let container = BinContainer::new(TEST_INPUT);
+        let res = container.life_support_rating();
+        assert_eq!(res, 230);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 3);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 2954600);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 1662846);
+    }
+}
@@ -0,0 +1,373 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    bits::BitSet,
+    parse::{ParseError, Parser},
+    repl::{self, Command},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::debug;
+
+const HORIZ_SIZE: usize = 5;
+const VERT_SIZE: usize = 5;
+const BOARD_SIZE: usize = HORIZ_SIZE * VERT_SIZE;
+const BOARDS_MAX: usize = 100;
+
+#[derive(Debug, PartialEq)]
+enum Direction {
+    Row,
+    Col,
+}
+
+type BingoNumbers = Vec<u8>;
+type Boards = Vec<u8>;
+
+#[inline(always)]
+fn is_row_checked(marks: &BitSet, board: usize, nr: usize) -> bool {
+    let board_start = board * BOARD_SIZE;
+
+    let row_start = nr * HORIZ_SIZE + board_start;
+    let row_end = row_start + HORIZ_SIZE;
+
+    (row_start..row_end).all(|idx| marks.contains(idx))
+}
+
+#[inline(always)]
+fn is_col_checked(marks: &BitSet, board: usize, nr: usize) -> bool {
+    let board_start = board * BOARD_SIZE;
+
+    let col_start = nr + board_start;
+
+    (0..VERT_SIZE).all(|i| marks.contains(col_start + i * HORIZ_SIZE))
+}
+
+/// [`fill_boards`] with the configured `boards_max`
+fn read_boards(input: &str, config: &Config) -> Result<(BingoNumbers, Boards, usize), SolveError> {
+    Ok(fill_boards(
+        input,
+        config.param_or("boards_max", BOARDS_MAX)?,
+    )?)
+}
+
+/// The called numbers, the boards row by row and the board count
+#[inline(always)]
+fn fill_boards(
+    input: &str,
+    boards_max: usize,
+) -> Result<(BingoNumbers, Boards, usize), ParseError> {
+    let parser = Parser::new(input);
+    let mut records = parser.records();
+    // first line has called bingo numbers, separated by comma
+    let numbers = records
+        .next()
+        .ok_or_else(|| parser.error("no bingo numbers"))?
+        .numbers(',')?;
+
+    let mut board_count = 0usize;
+    let mut boards = Vec::with_capacity(BOARD_SIZE * boards_max);
+    for board in records {
+        if board_count == boards_max {
+            return Err(board.error(format!("more than {} boards", boards_max)));
+        }
+        if board.lines().count() != VERT_SIZE {
+            return Err(board.error(format!("a board has {} rows", VERT_SIZE)));
+        }
+        for row in board.lines() {
+            let row_numbers = row.numbers::<u8>(' ')?;
+            if row_numbers.len() != HORIZ_SIZE {
+                return Err(row.error(format!("a row has {} numbers", HORIZ_SIZE)));
+            }
+            boards.extend(row_numbers);
+        }
+        board_count += 1;
+    }
+
+    Ok((numbers, boards, board_count))
+}
+
+#[inline(always)]
+fn find_bingo(
+    boards: &Boards,
+    board_count: usize,
+    numbers: &BingoNumbers,
+) -> Result<(usize, usize, Direction, u32), String> {
+    let mut boards_check = BitSet::with_capacity(boards.len());
+
+    for number in numbers {
+        mark(boards, &mut boards_check, *number);
+
+        for board_nr in 0..board_count {
+            // TODO: this works because HORIZ_SIZE == VERT_SIZE
+            for row_nr in 0..HORIZ_SIZE {
+                let col_nr = row_nr;
+                if is_row_checked(&boards_check, board_nr, row_nr) {
+                    debug!(board_nr, row_nr, number = %number, "bingo in row");
+                    let res = calc_result(boards, board_nr, &boards_check, *number);
+                    return Ok((board_nr, row_nr, Direction::Row, res));
+                } else if is_col_checked(&boards_check, board_nr, col_nr) {
+                    debug!(board_nr, col_nr, number = %number, "bingo in col");
+                    let res = calc_result(boards, board_nr, &boards_check, *number);
+                    return Ok((board_nr, col_nr, Direction::Col, res));
+                }
+            }
+        }
+    }
+
+    Err("no bingo found".into())
+}
+
+#[inline(always)]
+fn find_last_bingo(
+    boards: &Boards,
+    board_count: usize,
+    numbers: &BingoNumbers,
+) -> Result<(usize, usize, Direction, u32), String> {
+    let mut boards_check = BitSet::with_capacity(boards.len());
+    let mut boards_finished = BitSet::with_capacity(board_count);
+    let mut last_board = None;
+    let mut last_number = 0u8;
+
+    for number in numbers {
+        mark(boards, &mut boards_check, *number);
+
+        for board_nr in 0..board_count {
+            if boards_finished.contains(board_nr) {
+                continue;
+            }
+            // TODO: this works because HORIZ_SIZE == VERT_SIZE
+            for row_nr in 0..HORIZ_SIZE {
+                let col_nr = row_nr;
+                if is_row_checked(&boards_check, board_nr, row_nr) {
+                    boards_finished.insert(board_nr);
+                    last_board.replace((board_nr, row_nr, Direction::Row));
+                    break;
+                } else if is_col_checked(&boards_check, board_nr, col_nr) {
+                    boards_finished.insert(board_nr);
+                    last_board.replace((board_nr, col_nr, Direction::Col));
+                    break;
+                }
+            }
+        }
+
+        if boards_finished.len() == board_count {
+            last_number = *number;
+            break;
+        }
+    }
+
+    if let Some(board_data) = last_board {
+        let (board_nr, row_nr, direction) = board_data;
+        debug!(board_nr, row_nr, ?direction, number = %last_number, "last bingo");
+        let res = calc_result(boards, board_nr, &boards_check, last_number);
+        return Ok((board_nr, row_nr, direction, res));
+    }
+    Err("no last bingo found".into())
+}
+
+/// Marks `number` on every board
+#[inline(always)]
+fn mark(boards: &Boards, marks: &mut BitSet, number: u8) {
+    for (idx, _) in boards.iter().enumerate().filter(|(_, &nr)| nr == number) {
+        marks.insert(idx);
+    }
+}
+
+#[inline(always)]
+fn calc_result(boards: &Boards, board_nr: usize, boards_check: &BitSet, bingo_nr: u8) -> u32 {
+    let board_start = board_nr * BOARD_SIZE;
+    let board_end = board_start + BOARD_SIZE;
+
+    let nr_sum = (board_start..board_end)
+        .filter(|&idx| !boards_check.contains(idx))
+        .map(|idx| boards[idx] as u32)
+        .sum::<u32>();
+    nr_sum * bingo_nr as u32
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let (numbers, boards, board_count) = read_boards(input, config)?;
+
+        let (_board_nr, _row_nr, _direction, result) =
+            find_bingo(&boards, board_count, &numbers).map_err(SolveError::NoSolution)?;
+
+        Ok(Answer::from(result))
+    }
+
+    fn part2(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let (numbers, boards, board_count) = read_boards(input, config)?;
+
+        let (_board_nr, _row_nr, _direction, result) =
+            find_last_bingo(&boards, board_count, &numbers).map_err(SolveError::NoSolution)?;
+
+        Ok(Answer::from(result))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        // called numbers, then boards separated by empty lines
+        let mut lines = input.lines();
+        lines
+            .next()
+            .is_some_and(|line| line.split(',').all(|nr| nr.trim().parse::<u8>().is_ok()))
+            && lines.next().is_some_and(|line| line.trim().is_empty())
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let (numbers, boards, _board_count) = fill_boards(input, BOARDS_MAX)?;
+        let boards = boards
+            .chunks(BOARD_SIZE)
+            .map(|board| board.chunks(HORIZ_SIZE).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!({ "numbers": numbers, "boards": boards }))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![
+            Command::new(
+                "numbers",
+                "",
+                "called bingo numbers",
+                |input, _args, config| {
+                    let (numbers, _boards, _board_count) =
+                        read_boards(input, config).map_err(|err| err.to_string())?;
+                    Ok(numbers
+                        .iter()
+                        .map(u8::to_string)
+                        .collect::<Vec<_>>()
+                        .join(","))
+                },
+            ),
+            Command::new(
+                "board",
+                "<nr>",
+                "one of the boards",
+                |input, args, config| {
+                    let (_numbers, boards, board_count) =
+                        read_boards(input, config).map_err(|err| err.to_string())?;
+                    let board_nr = repl::arg::<usize>(args, 0, "nr")?;
+                    if board_nr >= board_count {
+                        return Err(format!("there are {} boards", board_count));
+                    }
+                    let start = board_nr * BOARD_SIZE;
+                    Ok(boards[start..start + BOARD_SIZE]
+                        .chunks(HORIZ_SIZE)
+                        .map(|row| {
+                            row.iter()
+                                .map(|nr| format!("{:>2}", nr))
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"))
+                },
+            ),
+            Command::new(
+                "bingo",
+                "",
+                "first winning board, row or column and score",
+                |input, _args, config| {
+                    let (numbers, boards, board_count) =
+                        read_boards(input, config).map_err(|err| err.to_string())?;
+                    Ok(format!("{:?}", find_bingo(&boards, board_count, &numbers)?))
+                },
+            ),
+            Command::new(
+                "last_bingo",
+                "",
+                "last winning board, row or column and score",
+                |input, _args, config| {
+                    let (numbers, boards, board_count) =
+                        read_boards(input, config).map_err(|err| err.to_string())?;
+                    Ok(format!(
+                        "{:?}",
+                        find_last_bingo(&boards, board_count, &numbers)?
+                    ))
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    #[test]
+    fn test_find_bingo() {
+        let Some(input) = stored_input(&Config::new(2021, 4).input_path()) else {
+            return;
+        };
+        let (_numbers, boards, board_count) = fill_boards(&input, BOARDS_MAX).unwrap();
+        let numbers = vec![66, 78, 7, 45, 92];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (0, 0, Direction::Row, 103408u32));
+
+        let numbers = vec![39, 38, 62, 81, 77];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (0, 1, Direction::Row, 85855u32));
+
+        let numbers = vec![47, 66, 71, 17, 69];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (1, 0, Direction::Col, 71829u32));
+
+        let numbers = vec![11, 6, 83, 91, 87, 38];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (2, 4, Direction::Col, 42028u32));
+
+        let numbers = vec![11, 6, 83, 91, 87, 38];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (2, 4, Direction::Col, 42028u32));
+
+        let numbers = vec![11, 6, 73, 81, 87, 8, 1, 2, 59, 7, 16, 3];
+        let res = find_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (6, 2, Direction::Col, 14912u32));
+    }
+
+    #[test]
+    fn test_fill_boards() {
+        let input = "7,4,9\n\n22 13 17 11  0\n 8  2 23  4 24\n";
+        assert_eq!(
+            fill_boards(input, BOARDS_MAX).unwrap_err().to_string(),
+            "line 3, column 1: a board has 5 rows"
+        );
+        let input = "7,4,9\n\n1 2 3 4 5\n6 7 8 9 10\n1 2 3 4 5\n6 7 8 9\n1 2 3 4 5";
+        assert_eq!(
+            fill_boards(input, BOARDS_MAX).unwrap_err().to_string(),
+            "line 6, column 1: a row has 5 numbers"
+        );
+    }
+
+    #[test]
+    fn test_find_last_bingo() {
+        let Some(input) = stored_input(&Config::new(2021, 4).input_path()) else {
+            return;
+        };
+        let (numbers, boards, board_count) = fill_boards(&input, BOARDS_MAX).unwrap();
+        let res = find_last_bingo(&boards, board_count, &numbers);
+        assert_eq!(res.unwrap(), (58, 2, Direction::Row, 7686u32));
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 4);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 34506);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 7686);
+    }
+}
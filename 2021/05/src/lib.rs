@@ -0,0 +1,229 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use std::collections::HashMap;
+
+use aoc_common::{
+    geometry::{Point, Segment},
+    parse::{ParseError, Parser},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::warn;
+
+/// Number of vents on every point
+struct VentureHandler {
+    fields: HashMap<Point, u8>,
+}
+
+impl VentureHandler {
+    fn new() -> Self {
+        Self {
+            fields: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, point: Point) {
+        *self.fields.entry(point).or_insert(0) += 1;
+    }
+
+    #[allow(dead_code)]
+    fn status(&self, point: &Point) -> u8 {
+        self.fields.get(point).copied().unwrap_or(0)
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, input, false)?;
+        Ok(Answer::from(count_venture_points(&handler, 2)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, input, true)?;
+        Ok(Answer::from(count_venture_points(&handler, 2)))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let ventures = read_ventures(input)?
+            .iter()
+            .map(|venture| {
+                [
+                    [venture.start.x, venture.start.y],
+                    [venture.end.x, venture.end.y],
+                ]
+            })
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!(ventures))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| line.contains(" -> "))
+    }
+}
+
+/// `x,y`
+fn parse_point(parser: &mut Parser) -> Result<Point, ParseError> {
+    let (x, y) = parser.pair(',')?;
+    Ok(Point::new(x, y))
+}
+
+/// `x1,y1 -> x2,y2`
+fn read_ventures(input: &str) -> Result<Vec<Segment>, ParseError> {
+    Parser::new(input).each_line(|line| {
+        let start = parse_point(line)?;
+        line.tag("->")?;
+        Ok(Segment::new(start, parse_point(line)?))
+    })
+}
+
+fn calc_ventures(
+    handler: &mut VentureHandler,
+    input: &str,
+    diagonal: bool,
+) -> Result<(), ParseError> {
+    for venture in read_ventures(input)? {
+        if venture.is_horizontal() || venture.is_vertical() || (diagonal && venture.is_diagonal()) {
+            venture.points().for_each(|point| handler.push(point));
+        } else if diagonal {
+            warn!(
+                start = %venture.start,
+                end = %venture.end,
+                "ignoring venture, neither straight nor diagonal"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+fn count_venture_points(handler: &VentureHandler, min_power: u8) -> usize {
+    handler
+        .fields
+        .values()
+        .filter(|&&status| status >= min_power)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"0,9 -> 5,9
+        8,0 -> 0,8
+        9,4 -> 3,4
+        2,2 -> 2,1
+        7,0 -> 7,4
+        6,4 -> 2,0
+        0,9 -> 2,9
+        3,4 -> 1,4
+        0,0 -> 8,8
+        5,5 -> 8,2";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 5).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_calc_ventures() {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, TEST_INPUT, false).unwrap();
+        let status = handler.status(&Point::new(1, 4));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(2, 4));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(3, 4));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(4, 4));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(8, 0));
+        assert_eq!(status, 0);
+        let status = handler.status(&Point::new(7, 4));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(0, 9));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(7, 9));
+        assert_eq!(status, 0);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut handler = VentureHandler::new();
+        assert_eq!(
+            calc_ventures(&mut handler, "0,9 -> 5,9\n8,0 -> 0;8", false)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 9: expected ',', found ';8'"
+        );
+    }
+
+    #[test]
+    fn test_count_venture_points() {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, TEST_INPUT, false).unwrap();
+        let count = count_venture_points(&handler, 2);
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn test_calc_ventures_diagonal() {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, TEST_INPUT, true).unwrap();
+        let status = handler.status(&Point::new(1, 4));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(2, 4));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(3, 4));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(4, 4));
+        assert_eq!(status, 3);
+        let status = handler.status(&Point::new(8, 0));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(7, 4));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(0, 9));
+        assert_eq!(status, 2);
+        let status = handler.status(&Point::new(7, 9));
+        assert_eq!(status, 0);
+        let status = handler.status(&Point::new(0, 8));
+        assert_eq!(status, 1);
+        let status = handler.status(&Point::new(1, 8));
+        assert_eq!(status, 0);
+        let status = handler.status(&Point::new(8, 8));
+        assert_eq!(status, 1);
+    }
+
+    #[test]
+    fn test_count_venture_points_diagonal() {
+        let mut handler = VentureHandler::new();
+        calc_ventures(&mut handler, TEST_INPUT, true).unwrap();
+        let count = count_venture_points(&handler, 2);
+        assert_eq!(count, 12);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 5);
+        assert_eq!(Day.part1(TEST_INPUT, &config).unwrap(), 5);
+        assert_eq!(Day.part2(TEST_INPUT, &config).unwrap(), 12);
+    }
+}
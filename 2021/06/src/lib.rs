@@ -0,0 +1,246 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    diff::{Rng, Variant},
+    math::Matrix,
+    parse::{ParseError, Parser},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::trace;
+
+type FishType = usize;
+
+const TIMERS: usize = 9;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let fish = read_fish_population(input)?;
+        Ok(Answer::from(count(&fish_after(&fish, 80))))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let fish = read_fish_population(input)?;
+        Ok(Answer::from(count(&fish_after(&fish, 256))))
+    }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant::new(1, "earth_rotation", |input, _config| {
+                let mut fish = read_fish_population(input)?;
+                earth_rotation(&mut fish, 80);
+                Ok(Answer::from(count(&fish)))
+            }),
+            Variant::new(2, "earth_rotation", |input, _config| {
+                let mut fish = read_fish_population(input)?;
+                earth_rotation(&mut fish, 256);
+                Ok(Answer::from(count(&fish)))
+            }),
+        ]
+    }
+
+    fn examples(&self) -> Vec<&'static str> {
+        vec!["3,4,3,1,2"]
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        // the initial timers of the puzzle are 1 to 5
+        let timers = (0..rng.range(1, 300))
+            .map(|_| rng.range(1, 6).to_string())
+            .collect::<Vec<_>>();
+        Some(timers.join(","))
+    }
+
+    /// Number of fish per reproduction timer 0 to 8
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        Ok(serde_json::json!(read_fish_population(input)?))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().count() == 1
+            && input
+                .trim()
+                .split(',')
+                .all(|timer| matches!(timer.parse::<u8>(), Ok(0..=8)))
+    }
+}
+
+#[inline(always)]
+fn read_fish_population(input: &str) -> Result<Vec<FishType>, ParseError> {
+    let mut fish: Vec<FishType> = vec![0; TIMERS];
+
+    for mut reproduction_timer in Parser::new(input).split(',') {
+        fish[reproduction_timer.number_in(0..=8)?] += 1;
+        reproduction_timer.end()?;
+    }
+
+    Ok(fish)
+}
+
+fn birth(fish: &mut [FishType]) {
+    // midnight birth
+    let fish_born = fish[0];
+    for day in 1..=8 {
+        fish[day - 1] = fish[day];
+    }
+    fish[6] += fish_born;
+    fish[8] = fish_born;
+}
+
+/// Tomorrow's fish per timer from today's, row by column
+fn birth_matrix() -> Matrix<FishType, TIMERS> {
+    let mut rows = [[0; TIMERS]; TIMERS];
+    for day in 1..TIMERS {
+        rows[day - 1][day] = 1;
+    }
+    // midnight birth
+    rows[6][0] = 1;
+    rows[8][0] = 1;
+    Matrix::new(rows)
+}
+
+/// Fish per timer after `days`, a power of the birth matrix in log time
+fn fish_after(fish: &[FishType], days: u16) -> [FishType; TIMERS] {
+    let fish = std::array::from_fn(|timer| fish[timer]);
+    birth_matrix().pow(days as u64).apply(&fish)
+}
+
+fn earth_rotation(fish: &mut [FishType], days: u16) {
+    for day in 1..=days {
+        birth(fish);
+        trace!(day, fish = count(fish), "rotation");
+    }
+}
+
+#[inline(always)]
+fn count(fish: &[FishType]) -> FishType {
+    fish.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{diff, input::stored_input};
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"3,4,3,1,2";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 6).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_read_fish_population() {
+        let fish = read_fish_population(TEST_INPUT).unwrap();
+        assert_eq!(fish[0], 0);
+        assert_eq!(fish[1], 1);
+        assert_eq!(fish[2], 1);
+        assert_eq!(fish[3], 2);
+        assert_eq!(fish[4], 1);
+        assert_eq!(fish[5], 0);
+        assert_eq!(fish[6], 0);
+        assert_eq!(fish[7], 0);
+        assert_eq!(fish[8], 0);
+    }
+
+    #[test]
+    fn test_read_fish_population_error() {
+        assert_eq!(
+            read_fish_population("3,4,3,\n1,9").unwrap_err().to_string(),
+            "line 2, column 3: number 9 not in 0..=8"
+        );
+    }
+
+    #[test]
+    fn test_birth() {
+        let mut fish = read_fish_population(TEST_INPUT).unwrap();
+        assert_eq!(fish.len(), 9);
+
+        birth(&mut fish);
+        assert_eq!(fish[0], 1);
+        assert_eq!(fish[1], 1);
+        assert_eq!(fish[2], 2);
+        assert_eq!(fish[3], 1);
+        assert_eq!(fish[4], 0);
+        assert_eq!(fish[5], 0);
+        assert_eq!(fish[6], 0);
+        assert_eq!(fish[7], 0);
+        assert_eq!(fish[8], 0);
+
+        birth(&mut fish);
+        assert_eq!(fish[0], 1);
+        assert_eq!(fish[1], 2);
+        assert_eq!(fish[2], 1);
+        assert_eq!(fish[3], 0);
+        assert_eq!(fish[4], 0);
+        assert_eq!(fish[5], 0);
+        assert_eq!(fish[6], 1);
+        assert_eq!(fish[7], 0);
+        assert_eq!(fish[8], 1);
+    }
+
+    #[test]
+    fn test_earth_rotation() {
+        let mut fish = read_fish_population(TEST_INPUT).unwrap();
+        earth_rotation(&mut fish, 5);
+        assert_eq!(count(&fish), 10);
+        earth_rotation(&mut fish, 10 - 5);
+        assert_eq!(count(&fish), 12);
+        earth_rotation(&mut fish, 14 - 10);
+        assert_eq!(count(&fish), 20);
+        earth_rotation(&mut fish, 18 - 14);
+        assert_eq!(count(&fish), 26);
+        earth_rotation(&mut fish, 80 - 18);
+        assert_eq!(count(&fish), 5934);
+    }
+
+    #[test]
+    fn test_earth_rotation_256days() {
+        let mut fish = read_fish_population(TEST_INPUT).unwrap();
+        earth_rotation(&mut fish, 256);
+        assert_eq!(count(&fish), 26984457539);
+    }
+
+    #[test]
+    fn test_fish_after() {
+        let fish = read_fish_population(TEST_INPUT).unwrap();
+        assert_eq!(fish_after(&fish, 0).to_vec(), fish);
+        assert_eq!(fish_after(&fish, 2), [1, 2, 1, 0, 0, 0, 1, 0, 1]);
+        assert_eq!(count(&fish_after(&fish, 18)), 26);
+        assert_eq!(count(&fish_after(&fish, 256)), 26984457539);
+    }
+
+    #[test]
+    fn test_variants() {
+        let config = Config::new(2021, 6);
+        match diff::check(&Day, &config, 50, 0) {
+            Ok(summary) => assert_eq!(summary.inputs, 51),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 6);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 360268);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 1632146183902u64);
+    }
+}
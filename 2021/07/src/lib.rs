@@ -0,0 +1,265 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    math,
+    parse::{ParseError, Parser},
+    repl::{self, Command},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::debug;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let (positions, pos) = get_lowcost_position(input)?;
+        Ok(Answer::from(calc_cost(&positions, pos)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let (_positions, _pos, cost) = get_lowcost_position2(input)?;
+        Ok(Answer::from(cost))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().count() == 1
+            && input
+                .trim()
+                .split(',')
+                .all(|pos| pos.parse::<u16>().is_ok())
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        Ok(serde_json::json!(get_lowcost_position(input)?.0))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![
+            Command::new(
+                "lowcost_position",
+                "",
+                "median position of part 1",
+                |input, _args, _config| {
+                    let (_positions, pos) =
+                        get_lowcost_position(input).map_err(|err| err.to_string())?;
+                    Ok(pos.to_string())
+                },
+            ),
+            Command::new(
+                "lowcost_position2",
+                "",
+                "position and cost of part 2",
+                |input, _args, _config| {
+                    let (_positions, pos, cost) =
+                        get_lowcost_position2(input).map_err(|err| err.to_string())?;
+                    Ok(format!("position {}, cost {}", pos, cost))
+                },
+            ),
+            Command::new(
+                "calc_cost",
+                "<pos>",
+                "fuel to align at pos, part 1",
+                |input, args, _config| {
+                    let (positions, _pos) =
+                        get_lowcost_position(input).map_err(|err| err.to_string())?;
+                    Ok(calc_cost(&positions, repl::arg(args, 0, "pos")?).to_string())
+                },
+            ),
+            Command::new(
+                "calc_cost2",
+                "<pos>",
+                "fuel to align at pos, part 2",
+                |input, args, _config| {
+                    let (positions, _pos) =
+                        get_lowcost_position(input).map_err(|err| err.to_string())?;
+                    Ok(calc_cost2(&positions, repl::arg(args, 0, "pos")?).to_string())
+                },
+            ),
+        ]
+    }
+}
+
+fn get_lowcost_position(input: &str) -> Result<(Vec<u16>, u16), ParseError> {
+    let parser = Parser::new(input);
+    let mut lines = parser.lines();
+    let positions = lines
+        .next()
+        .ok_or_else(|| parser.error("no crab positions"))?
+        .numbers::<u16>(',')?;
+    if let Some(line) = lines.next() {
+        return Err(line.error("expected the crab positions on one line"));
+    }
+
+    let crab_count = positions.len() as u16;
+
+    let mut mean_vec = positions.clone();
+    mean_vec.sort_unstable();
+    if crab_count.is_multiple_of(2) {
+        Ok((
+            positions,
+            (mean_vec[crab_count as usize / 2 - 1] + mean_vec[crab_count as usize / 2]) / 2,
+        ))
+    } else {
+        Ok((positions, mean_vec[crab_count as usize / 2]))
+    }
+}
+
+fn get_lowcost_position2(input: &str) -> Result<(Vec<u16>, u16, u32), ParseError> {
+    let p = get_lowcost_position(input)?;
+    let mut positions = p.0;
+    positions.sort_unstable();
+    let mean = p.1;
+
+    let (min, max) = (positions[0], positions[positions.len() - 1]);
+
+    // starting at mean pos and decide for bigger side
+    let go_up = max - mean >= mean - min;
+
+    let mut pos = mean;
+    let mut cur_cost = calc_cost2(&positions, pos);
+    let mut check_cost = cur_cost;
+
+    if go_up {
+        while pos < max {
+            cur_cost = check_cost;
+            check_cost = calc_cost2(&positions, pos + 1);
+            if check_cost > cur_cost {
+                break;
+            }
+            pos += 1;
+        }
+    } else {
+        // go down
+        while pos > min {
+            cur_cost = check_cost;
+            check_cost = calc_cost2(&positions, pos - 1);
+            if check_cost > cur_cost {
+                break;
+            }
+            pos -= 1;
+        }
+    }
+
+    debug!(mean, go_up, pos, cost = cur_cost, "lowest cost position");
+
+    Ok((positions, pos, cur_cost))
+}
+
+fn calc_cost(positions: &[u16], pos: u16) -> u32 {
+    positions
+        .iter()
+        .map(|&p| {
+            // moves
+            if p > pos {
+                (p - pos) as u32
+            } else {
+                (pos - p) as u32
+            }
+        })
+        .sum::<u32>()
+}
+
+#[inline(always)]
+fn calc_cost2(positions: &[u16], pos: u16) -> u32 {
+    positions
+        .iter()
+        .map(|&p| {
+            // moves
+            let diff = if p > pos {
+                (p - pos) as u32
+            } else {
+                (pos - p) as u32
+            };
+            math::triangular(diff).expect("a triangular number of u16 fits in u32")
+        })
+        .sum::<u32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 7).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_get_lowcost_position() {
+        let pos = get_lowcost_position(TEST_INPUT).unwrap();
+        assert_eq!(pos.1, 2);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            get_lowcost_position("").unwrap_err().to_string(),
+            "line 1, column 1: no crab positions"
+        );
+        assert_eq!(
+            get_lowcost_position("16,1,x").unwrap_err().to_string(),
+            "line 1, column 6: expected a number, found 'x'"
+        );
+        assert_eq!(
+            get_lowcost_position("16,1,2\n0,4").unwrap_err().to_string(),
+            "line 2, column 1: expected the crab positions on one line"
+        );
+    }
+
+    #[test]
+    fn test_calc_cost() {
+        let pos = get_lowcost_position(TEST_INPUT).unwrap();
+        let cost = calc_cost(&pos.0, pos.1);
+        assert_eq!(cost, 37);
+    }
+
+    #[test]
+    fn test_get_lowcost_position2() {
+        let pos = get_lowcost_position2(TEST_INPUT).unwrap();
+        assert_eq!(pos.1, 5);
+        assert_eq!(pos.2, 168);
+    }
+
+    #[test]
+    fn test_single_crab() {
+        assert_eq!(get_lowcost_position("5").unwrap().1, 5);
+        assert_eq!(get_lowcost_position("1,2,10").unwrap().1, 2);
+        let pos = get_lowcost_position2("5").unwrap();
+        assert_eq!((pos.1, pos.2), (5, 0));
+    }
+
+    #[test]
+    fn test_large_positions() {
+        let pos = get_lowcost_position("1000,2000,3000").unwrap();
+        assert_eq!((pos.1, calc_cost(&pos.0, pos.1)), (2000, 2000));
+        let pos = get_lowcost_position2("1000,2000,3000").unwrap();
+        assert_eq!((pos.1, pos.2), (2000, 1001000));
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 7);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 355764);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 99634572);
+    }
+}
@@ -0,0 +1,366 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use std::collections::HashMap;
+
+use aoc_common::{
+    graph::Graph,
+    parse::{ParseError, Parser},
+    repl::Command,
+    Answer, Config, Solution, SolveError,
+};
+use tracing::{debug, trace};
+
+/// Heights by row and column
+type Heights = Vec<Vec<u8>>;
+type PointsLow = Vec<Point>;
+type MapNiner = HashMap<usize, HashMap<usize, Point>>;
+/// Locations below 9 by `(x, y)` with their height, adjacent ones connected
+type Basins = Graph<(usize, usize), u8>;
+
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    pub x: usize,
+    pub y: usize,
+    pub value: u8,
+}
+
+impl Point {
+    fn new(x: usize, y: usize, value: u8) -> Self {
+        Self { x, y, value }
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        // ignore value
+        self.x == other.x && self.y == other.y
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let (points, _map_yx_niners) = point_locations(&heights(input)?);
+        Ok(Answer::from(calc_risk_sum(&points)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let heights = heights(input)?;
+        let (points, _map_yx_niners) = point_locations(&heights);
+        let mut bsizes = basin_sizes(&points, &basin_graph(&heights));
+        bsizes.sort_unstable();
+        bsizes.reverse();
+
+        if bsizes.len() < 3 {
+            return Err(SolveError::NoSolution("less than 3 basins".to_string()));
+        }
+        Ok(Answer::from(bsizes[0..3].iter().product::<usize>()))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        Ok(serde_json::json!(heights(input)?))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        // a height map, not just binary numbers
+        let width = input.lines().next().map_or(0, |line| line.trim().len());
+        input.lines().all(|line| {
+            let line = line.trim();
+            line.len() == width && line.chars().all(|c| c.is_ascii_digit())
+        }) && input.chars().any(|c| ('2'..='9').contains(&c))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![
+            Command::new(
+                "low_points",
+                "",
+                "the low points",
+                |input, _args, _config| {
+                    let heights = heights(input).map_err(|err| err.to_string())?;
+                    Ok(format!("{:#?}", point_locations(&heights).0))
+                },
+            ),
+            Command::new(
+                "niners",
+                "",
+                "the map of nines by y and x",
+                |input, _args, _config| {
+                    let heights = heights(input).map_err(|err| err.to_string())?;
+                    let (_points, map_yx_niners) = point_locations(&heights);
+                    // sorted, the hash map order changes every run
+                    let mut lines = map_yx_niners
+                        .iter()
+                        .map(|(y, niners)| {
+                            let mut xs = niners.keys().collect::<Vec<_>>();
+                            xs.sort_unstable();
+                            (y, xs)
+                        })
+                        .collect::<Vec<_>>();
+                    lines.sort_unstable();
+                    Ok(lines
+                        .iter()
+                        .map(|(y, xs)| format!("{:>3}: {:?}", y, xs))
+                        .collect::<Vec<_>>()
+                        .join("\n"))
+                },
+            ),
+            Command::new(
+                "basin_sizes",
+                "",
+                "size of every basin",
+                |input, _args, _config| {
+                    let heights = heights(input).map_err(|err| err.to_string())?;
+                    let (points, _map_yx_niners) = point_locations(&heights);
+                    Ok(format!(
+                        "{:?}",
+                        basin_sizes(&points, &basin_graph(&heights))
+                    ))
+                },
+            ),
+        ]
+    }
+}
+
+fn heights(input: &str) -> Result<Heights, ParseError> {
+    let mut width = None;
+    Parser::new(input).each_line(|line| {
+        let start = *line;
+        let mut row = Vec::new();
+        while !line.is_empty() {
+            let at = *line;
+            let height = line.char()?;
+            let height = height
+                .to_digit(10)
+                .ok_or_else(|| at.error(format!("invalid height '{}'", height)))?;
+            row.push(height as u8);
+        }
+        match *width.get_or_insert(row.len()) {
+            width if width != row.len() => {
+                Err(start.error(format!("expected {} heights, found {}", width, row.len())))
+            }
+            _ => Ok(row),
+        }
+    })
+}
+
+fn point_locations(heights: &Heights) -> (PointsLow, MapNiner) {
+    let mut points = PointsLow::new();
+    let mut map_yx_niners = MapNiner::new();
+
+    for (y, row) in heights.iter().enumerate() {
+        for (x, &value) in row.iter().enumerate() {
+            if value == 9 {
+                map_yx_niners
+                    .entry(y)
+                    .or_default()
+                    .insert(x, Point::new(x, y, value));
+            }
+
+            let left = x.checked_sub(1).map(|x| row[x]);
+            let right = row.get(x + 1).copied();
+            let up = y.checked_sub(1).map(|y| heights[y][x]);
+            let down = heights.get(y + 1).map(|row| row[x]);
+            if [left, right, up, down]
+                .into_iter()
+                .flatten()
+                .all(|neighbour| value < neighbour)
+            {
+                points.push(Point::new(x, y, value));
+            }
+        }
+    }
+
+    debug!(
+        low_points = points.len(),
+        niner_lines = map_yx_niners.len(),
+        "point locations"
+    );
+
+    (points, map_yx_niners)
+}
+
+#[inline(always)]
+fn calc_risk_sum(points: &PointsLow) -> u16 {
+    points.iter().map(|p| p.value as u16 + 1).sum::<u16>()
+}
+
+fn basin_graph(heights: &Heights) -> Basins {
+    let mut basins = Basins::new();
+    for (y, row) in heights.iter().enumerate() {
+        for (x, &value) in row.iter().enumerate().filter(|(_, &value)| value < 9) {
+            let id = basins.add_node_with((x, y), value);
+            // connect to the left and upper neighbours, added before
+            let left = x.checked_sub(1).map(|x| (x, y));
+            let up = y.checked_sub(1).map(|y| (x, y));
+            for neighbour in [left, up].into_iter().flatten() {
+                if let Some(other) = basins.id(&neighbour) {
+                    basins.add_edge(id, other, 1);
+                    basins.add_edge(other, id, 1);
+                }
+            }
+        }
+    }
+    basins
+}
+
+/// Size of the basin around every low point
+fn basin_sizes(points: &PointsLow, basins: &Basins) -> Vec<usize> {
+    points
+        .iter()
+        .map(|p| {
+            let size = basins.id(&(p.x, p.y)).map_or(0, |id| basins.bfs(id).len());
+            trace!(x = p.x, y = p.y, size, "basin");
+            size
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"2199943210
+                               3987894921
+                               9856789892
+                               8767896789
+                               9899965678";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 9).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_low_locations() {
+        let points = point_locations(&heights(TEST_INPUT).unwrap());
+        let points = points.0;
+
+        assert_eq!(points[0], Point::new(1, 0, 1));
+        assert_eq!(points[1], Point::new(9, 0, 0));
+        assert_eq!(points[2], Point::new(2, 2, 5));
+        assert_eq!(points[3], Point::new(6, 4, 5));
+    }
+
+    #[test]
+    fn test_niners() {
+        let points = point_locations(&heights(TEST_INPUT).unwrap());
+        let map_yx_niners = points.1;
+        // let _points = points.0;
+
+        assert_eq!(map_yx_niners.len(), 5);
+        assert_eq!(map_yx_niners.get(&0).unwrap().len(), 3);
+        assert_eq!(map_yx_niners.get(&1).unwrap().len(), 3);
+        assert_eq!(map_yx_niners.get(&2).unwrap().len(), 3);
+        assert_eq!(map_yx_niners.get(&3).unwrap().len(), 2);
+        assert_eq!(map_yx_niners.get(&4).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_niners_input() {
+        let Some(input) = stored_input(&Config::new(2021, 9).input_path()) else {
+            return;
+        };
+
+        let points = point_locations(&heights(&input).unwrap());
+        let map_yx_niners = points.1;
+
+        assert_eq!(map_yx_niners.len(), 100);
+        assert_eq!(map_yx_niners.get(&0).unwrap().len(), 24);
+        assert_eq!(map_yx_niners.get(&10).unwrap().len(), 27);
+
+        assert_eq!(
+            map_yx_niners.values().map(|v| { v.len() }).sum::<usize>(),
+            2815
+        );
+    }
+
+    #[test]
+    fn test_basin_sizes() {
+        let heights = heights(TEST_INPUT).unwrap();
+        let (points, _map_yx_niners) = point_locations(&heights);
+
+        let mut bsizes = basin_sizes(&points, &basin_graph(&heights));
+        bsizes.sort_unstable();
+        bsizes.reverse();
+
+        let basin_res = bsizes[0..3].iter().product::<usize>();
+
+        assert_eq!(basin_res, 1134)
+    }
+
+    #[test]
+    fn test_basin_graph() {
+        let basins = basin_graph(&heights(TEST_INPUT).unwrap());
+        assert_eq!(basins.len(), 50 - 15);
+        assert_eq!(basins.attr(basins.id(&(1, 0)).unwrap()), &1);
+        assert_eq!(basins.id(&(2, 0)), None);
+        // every basin is one component
+        assert_eq!(basins.components().len(), 4);
+    }
+
+    #[test]
+    fn test_basin_sizes_input() {
+        let Some(input) = stored_input(&Config::new(2021, 9).input_path()) else {
+            return;
+        };
+
+        let heights = heights(&input).unwrap();
+        let (points, _map_yx_niners) = point_locations(&heights);
+
+        let mut bsizes = basin_sizes(&points, &basin_graph(&heights));
+        bsizes.sort_unstable();
+        bsizes.reverse();
+
+        let basin_res = bsizes[0..3].iter().product::<usize>();
+
+        assert_eq!(basin_res, 931200);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            heights("2199\n39x7").unwrap_err().to_string(),
+            "line 2, column 3: invalid height 'x'"
+        );
+        assert_eq!(
+            heights("2199\n398").unwrap_err().to_string(),
+            "line 2, column 1: expected 4 heights, found 3"
+        );
+        let config = Config::new(2021, 9);
+        assert!(matches!(
+            Day.part1("2199\n39x7", &config),
+            Err(SolveError::Parse(_))
+        ));
+        assert!(matches!(
+            Day.part2("2199\n398", &config),
+            Err(SolveError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 9);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 506);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 931200);
+    }
+}
@@ -0,0 +1,374 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    diff::{Rng, Variant},
+    parse::Parser,
+    Answer, Config, Solution, SolveError,
+};
+
+const TAGS_OPEN: &[char] = &['(', '[', '{', '<'];
+const TAGS_CLOSE: &[char] = &[')', ']', '}', '>'];
+const TAG_SCORE: &[u16] = &[3, 57, 1197, 25137];
+const TAG_AUTO_SCORE: &[u16] = &[1, 2, 3, 4];
+
+const EXAMPLE: &str = r"[({(<(())[]>[[{[]{<()<>>
+                           [(()[<>])]({[<{<<[]>>(
+                           {([(<{}[<>[]}>{[]{[(<()>
+                           (((({<>}<{<{<>}{[]{[]{}
+                           [[<[([]))<([[{}[[()]]]
+                           [{[{({}]{}}([{[{{{}}([]
+                           {<[[]]>}<{[{[{[]{()[[[]
+                           [<(<(<(<{}))><([]([]()
+                           <{([([[(<>()){}]>(<<{{
+                           <{([{{}}[<[[[<>{}]]]>[]]
+                          ";
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let error_scores = input
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                validate_delimiter(line).err().map(|err| err.0)
+            })
+            .collect::<Vec<u16>>();
+
+        Ok(Answer::from(sum_scores(&error_scores)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let mut autocomplete_scores = input
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                autocomplete_delimiter(line).map(|scores| calc_autocomplete_score(&scores))
+            })
+            .collect::<Vec<u64>>();
+
+        if autocomplete_scores.is_empty() {
+            return Err(SolveError::NoSolution("no incomplete lines".to_string()));
+        }
+        autocomplete_scores.sort_unstable();
+        Ok(Answer::from(
+            autocomplete_scores[(autocomplete_scores.len() - 1) >> 1],
+        ))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let lines = Parser::new(input).each_line(|line| {
+            let tags = line.rest();
+            while !line.is_empty() {
+                let at = *line;
+                let tag = line.char()?;
+                if !TAGS_OPEN.contains(&tag) && !TAGS_CLOSE.contains(&tag) {
+                    return Err(at.error(format!("invalid tag '{}'", tag)));
+                }
+            }
+            Ok(tags)
+        })?;
+        Ok(serde_json::json!(lines))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| {
+            line.trim()
+                .chars()
+                .all(|c| TAGS_OPEN.contains(&c) || TAGS_CLOSE.contains(&c))
+        })
+    }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant::new(1, "scan_line", part1_scan),
+            Variant::new(2, "scan_line", part2_scan),
+        ]
+    }
+
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        let lines = (0..rng.range(1, 20))
+            .map(|_| {
+                let mut line = String::new();
+                let mut tags = Vec::new();
+                for _ in 0..rng.range(1, 40) {
+                    if tags.is_empty() || rng.chance(55) {
+                        let idx = rng.index(TAGS_OPEN.len());
+                        tags.push(idx);
+                        line.push(TAGS_OPEN[idx]);
+                    } else if rng.chance(5) {
+                        line.push(TAGS_CLOSE[rng.index(TAGS_CLOSE.len())]);
+                    } else {
+                        line.push(TAGS_CLOSE[tags.pop().unwrap()]);
+                    }
+                }
+                line
+            })
+            .collect::<Vec<_>>();
+
+        Some(lines.join("\n"))
+    }
+}
+
+fn part1_scan(input: &str, _config: &Config) -> Result<Answer, SolveError> {
+    let score = input
+        .lines()
+        .filter_map(|line| scan_line(line.trim()).err())
+        .map(|score| score as u32)
+        .sum::<u32>();
+
+    Ok(Answer::from(score))
+}
+
+fn part2_scan(input: &str, _config: &Config) -> Result<Answer, SolveError> {
+    let mut scores = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| scan_line(line).ok())
+        .map(|tags| calc_autocomplete_score(&tags))
+        .collect::<Vec<u64>>();
+
+    if scores.is_empty() {
+        return Err(SolveError::NoSolution("no incomplete lines".to_string()));
+    }
+    scores.sort_unstable();
+    Ok(Answer::from(scores[(scores.len() - 1) >> 1]))
+}
+
+/// One pass over a line, the score of the first illegal delimiter or the tags
+/// left open in closing order
+fn scan_line(s: &str) -> Result<Vec<u8>, u16> {
+    let mut tags = Vec::<u8>::new();
+
+    for c in s.chars() {
+        if let Some(idx) = TAGS_OPEN.iter().position(|open_c| *open_c == c) {
+            tags.push(idx as u8);
+        } else if let Some(idx) = TAGS_CLOSE.iter().position(|close_c| *close_c == c) {
+            if tags.pop() != Some(idx as u8) {
+                return Err(TAG_SCORE[idx]);
+            }
+        }
+    }
+
+    tags.reverse();
+    Ok(tags)
+}
+
+fn validate_delimiter(s: &str) -> Result<&str, (u16, String)> {
+    if s.is_empty() {
+        Ok(s)
+    } else {
+        let mut tags = Vec::<u8>::new();
+        let mut error_tag: Option<(char, u8)> = None;
+        if s.chars().any(|c| {
+            let is_invalid = if let Some(open_tag_idx) =
+                TAGS_OPEN.iter().position(|open_c| *open_c == c)
+            {
+                tags.push(open_tag_idx as u8);
+                false
+            } else {
+                if TAGS_CLOSE.contains(&c) {
+                    let idx = TAGS_CLOSE.iter().position(|close_c| *close_c == c).unwrap() as u8;
+                    if tags.last().is_none() || *tags.last().unwrap() != idx {
+                        error_tag.replace((c, idx));
+                        true
+                    } else {
+                        tags.pop();
+                        false
+                    }
+                } else {
+                    false
+                }
+            };
+            is_invalid
+        }) {
+            let score = TAG_SCORE[error_tag.unwrap().1 as usize];
+            Err((
+                score,
+                format!(
+                    "delimiter {} has no matching open tag",
+                    error_tag.unwrap().0
+                ),
+            ))
+        } else {
+            Ok(s)
+        }
+    }
+}
+
+#[inline(always)]
+fn sum_scores(scores: &[u16]) -> u32 {
+    scores.iter().map(|score| *score as u32).sum::<u32>()
+}
+
+fn autocomplete_delimiter(s: &str) -> Option<Vec<u8>> {
+    if s.is_empty() {
+        None
+    } else {
+        let mut tags = Vec::<u8>::new();
+        let mut error_tag: Option<(char, u8)> = None;
+        if s.chars().any(|c| {
+            let is_invalid = if let Some(open_tag_idx) =
+                TAGS_OPEN.iter().position(|open_c| *open_c == c)
+            {
+                tags.push(open_tag_idx as u8);
+                false
+            } else {
+                if TAGS_CLOSE.contains(&c) {
+                    let idx = TAGS_CLOSE.iter().position(|close_c| *close_c == c).unwrap() as u8;
+                    if tags.last().is_none() || *tags.last().unwrap() != idx {
+                        error_tag.replace((c, idx));
+                        true
+                    } else {
+                        tags.pop();
+                        false
+                    }
+                } else {
+                    false
+                }
+            };
+            is_invalid
+        }) {
+            None
+        } else {
+            tags.reverse();
+            Some(tags)
+        }
+    }
+}
+
+#[inline(always)]
+fn calc_autocomplete_score(tags: &[u8]) -> u64 {
+    let mut score = 0u64;
+
+    tags.iter().for_each(|tag| {
+        score = score.checked_mul(5).unwrap();
+        score = score
+            .checked_add(TAG_AUTO_SCORE[*tag as usize] as u64)
+            .unwrap();
+    });
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{diff, input::stored_input};
+
+    use super::*;
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 10).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_validate_delimiter() {
+        let input = r"()
+                           []
+                           ([])
+                           {()()()}
+                           <([{}])>
+                           [<>({}){}[([])<>]]
+                           (((((((((())))))))))
+                          ";
+        input.lines().for_each(|line| {
+            let line = line.trim();
+            let res = validate_delimiter(line);
+            assert!(res.is_ok(), "error: '{}'", res.unwrap_err().0);
+        });
+
+        let input = r"(]
+                           {()()()>
+                           (((()))}
+                           <([]){()}[{}])
+                          ";
+        input.lines().for_each(|line| {
+            let line = line.trim();
+            let res = validate_delimiter(line);
+            assert!(
+                line.is_empty() || res.is_err(),
+                "should be error: '{}'",
+                res.unwrap()
+            );
+        });
+
+        let input = EXAMPLE;
+        let error_scores = input
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                validate_delimiter(line).err().map(|err| err.0)
+            })
+            .collect::<Vec<u16>>();
+
+        assert_eq!(error_scores.len(), 5);
+        assert_eq!(sum_scores(&error_scores), 26397);
+    }
+
+    #[test]
+    fn test_autocomplete_score() {
+        let line = r"<{([{{}}[<[[[<>{}]]]>[]]"; // completes with ])}>
+
+        let score = if let Some(scores) = autocomplete_delimiter(line) {
+            calc_autocomplete_score(&scores)
+        } else {
+            0
+        };
+        assert_eq!(score, 294);
+    }
+
+    #[test]
+    fn test_autocomplete_delimiter() {
+        let input = EXAMPLE;
+        let mut autocomplete_scores = input
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                autocomplete_delimiter(line).map(|scores| calc_autocomplete_score(&scores))
+            })
+            .collect::<Vec<u64>>();
+
+        autocomplete_scores.sort_unstable();
+        let score = autocomplete_scores[(autocomplete_scores.len() - 1) >> 1];
+
+        assert_eq!(score, 288957);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 10);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 319233);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 1118976874);
+    }
+
+    #[test]
+    fn test_variants() {
+        let config = Config::new(2021, 10);
+        match diff::check(&Day, &config, 200, 0) {
+            Ok(summary) => assert_eq!(summary.inputs, 201),
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
@@ -0,0 +1,261 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    parse::{ParseError, Parser},
+    simulation::{Runner, Simulation},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::trace;
+
+const LCOUNT: usize = 10usize;
+const LLENGTH: usize = 10usize;
+/// Give up on part 2 after this many steps
+const MAX_STEPS: u64 = 100_000;
+
+/// Energy levels of the octopuses, row by row
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct Octopuses {
+    levels: Vec<u8>,
+    line_count: usize,
+    line_length: usize,
+}
+
+impl Octopuses {
+    fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Indexes of the up to 8 octopuses around `idx`
+    fn neighbours(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = (idx % self.line_length, idx / self.line_length);
+        (y.saturating_sub(1)..=(y + 1).min(self.line_count - 1))
+            .flat_map(move |ny| {
+                (x.saturating_sub(1)..=(x + 1).min(self.line_length - 1))
+                    .map(move |nx| ny * self.line_length + nx)
+            })
+            .filter(move |&neighbour| neighbour != idx)
+    }
+}
+
+impl Simulation for Octopuses {
+    /// Octopuses that flashed
+    type Metric = usize;
+
+    fn step(&mut self) -> usize {
+        let mut flashing = Vec::new();
+        for (idx, level) in self.levels.iter_mut().enumerate() {
+            *level += 1;
+            if *level == 10 {
+                flashing.push(idx);
+            }
+        }
+
+        // every octopus reaches 10 once at most, the levels beyond don't matter
+        while let Some(idx) = flashing.pop() {
+            for neighbour in self.neighbours(idx).collect::<Vec<_>>() {
+                self.levels[neighbour] += 1;
+                if self.levels[neighbour] == 10 {
+                    flashing.push(neighbour);
+                }
+            }
+        }
+
+        let mut flashes = 0;
+        for level in self.levels.iter_mut().filter(|level| **level > 9) {
+            *level = 0;
+            flashes += 1;
+        }
+        flashes
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let octopuses = detect_energy(input, config)?;
+
+        let flashes = Runner::new(octopuses).limit(100).sum::<usize>();
+        Ok(Answer::from(flashes))
+    }
+
+    fn part2(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let octopuses = detect_energy(input, config)?;
+        bright_flash_step(octopuses, config.param_or("max_steps", MAX_STEPS)?)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution("the octopuses never flash together".to_string()))
+    }
+
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let octopuses = detect_energy(input, &Config::new(2021, 11))?;
+        let rows = octopuses
+            .levels
+            .chunks(octopuses.line_length)
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!(rows))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        let width = input.lines().next().map_or(0, |line| line.trim().len());
+        input.lines().all(|line| {
+            let line = line.trim();
+            line.len() == width && line.chars().all(|c| c.is_ascii_digit())
+        })
+    }
+}
+
+/// The grid of `line_count` lines of `line_length` digits
+fn detect_energy(input: &str, config: &Config) -> Result<Octopuses, SolveError> {
+    let line_count = config.param_or("line_count", LCOUNT)?;
+    let line_length = config.param_or("line_length", LLENGTH)?;
+
+    let input = Parser::new(input);
+    let mut levels = Vec::with_capacity(line_count * line_length);
+    let mut lines = 0;
+    for mut line in input.lines() {
+        if lines == line_count {
+            return Err(line.error(format!("more than {} lines", line_count)).into());
+        }
+        if line.rest().len() != line_length {
+            return Err(line
+                .error(format!("expected {} levels", line_length))
+                .into());
+        }
+        while !line.is_empty() {
+            let at = line;
+            let level = line.char()?;
+            let level = level
+                .to_digit(10)
+                .ok_or_else(|| at.error(format!("invalid level '{}'", level)))?;
+            levels.push(level as u8);
+        }
+        lines += 1;
+    }
+    if lines < line_count {
+        return Err(ParseError {
+            line: lines + 1,
+            column: 1,
+            msg: format!("expected {} lines, found {}", line_count, lines),
+        }
+        .into());
+    }
+
+    Ok(Octopuses {
+        levels,
+        line_count,
+        line_length,
+    })
+}
+
+/// The first step where all octopuses flash, `None` if it takes more than
+/// `max_steps`
+fn bright_flash_step(octopuses: Octopuses, max_steps: u64) -> Option<u64> {
+    let count = octopuses.len();
+    Runner::new(octopuses)
+        .limit(max_steps)
+        .on_step(|step, _, flashes| trace!(step, flashes, "energy step"))
+        .position(|flashes| flashes == count)
+        .map(|idx| idx as u64 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"5483143223
+                               2745854711
+                               5264556173
+                               6141336146
+                               6357385478
+                               4167524645
+                               2176841721
+                               6882881134
+                               4846848554
+                               5283751526
+                              ";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 11).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    fn octopuses() -> Octopuses {
+        detect_energy(TEST_INPUT, &Config::new(2021, 11)).unwrap()
+    }
+
+    #[test]
+    fn test_detect_energy() {
+        let levels = octopuses().levels;
+        assert_eq!(levels[0], 5);
+        assert_eq!(levels[9], 3);
+        assert_eq!(levels[10], 2);
+        assert_eq!(levels[23], 4);
+        assert_eq!(levels[49], 8);
+        assert_eq!(levels[99], 6);
+
+        let config = Config::new(2021, 11);
+        assert_eq!(
+            detect_energy("5483143223\n27458547x1\n", &config).unwrap_err(),
+            SolveError::Parse("line 2, column 9: invalid level 'x'".to_string())
+        );
+        assert_eq!(
+            detect_energy("5483143223\n", &config).unwrap_err(),
+            SolveError::Parse("line 2, column 1: expected 10 lines, found 1".to_string())
+        );
+        assert!(detect_energy("548314322\n", &config).is_err());
+    }
+
+    #[test]
+    fn test_energy_step() {
+        let mut runner = Runner::new(octopuses());
+
+        let mut flashes = 0;
+        flashes += runner.step().unwrap();
+        assert_eq!(flashes, 0);
+
+        flashes += runner.step().unwrap();
+        assert_eq!(flashes, 35);
+
+        flashes += runner.by_ref().take(8).sum::<usize>();
+        assert_eq!(flashes, 204);
+
+        flashes += runner.by_ref().take(90).sum::<usize>();
+        assert_eq!(flashes, 1656);
+    }
+
+    #[test]
+    fn test_bright_flash_step() {
+        assert_eq!(bright_flash_step(octopuses(), MAX_STEPS), Some(195));
+        assert_eq!(bright_flash_step(octopuses(), 194), None);
+
+        // all at 0 after step 195, so it repeats every 10 steps from there
+        let cycle = Runner::new(octopuses()).find_cycle().unwrap();
+        assert_eq!(cycle.length, 10);
+        assert!(cycle.start <= 195);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 11);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 1667);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 488);
+    }
+}
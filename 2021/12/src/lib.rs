@@ -0,0 +1,523 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use std::collections::HashSet;
+
+use aoc_common::{
+    diff::{Rng, Variant},
+    graph::Graph,
+    parse::{ParseError, Parser},
+    repl::{self, Command},
+    Answer, Config, Solution, SolveError,
+};
+use tracing::trace;
+
+const END: &str = "end";
+const START: &str = "start";
+
+const SAMPLES: &[&str] = &[
+    r"start-A
+      start-b
+      A-c
+      A-b
+      b-d
+      A-end
+      b-end
+     ",
+    r"dc-end
+      HN-start
+      start-kj
+      dc-start
+      dc-HN
+      LN-dc
+      HN-end
+      kj-sa
+      kj-HN
+      kj-dc
+     ",
+    r"fs-end
+      he-DX
+      fs-he
+      start-DX
+      pj-DX
+      end-zg
+      zg-sl
+      zg-pj
+      pj-he
+      RW-he
+      fs-DX
+      pj-RW
+      zg-RW
+      start-pj
+      he-WI
+      zg-he
+      pj-fs
+      start-RW
+     ",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cave {
+    Small,
+    Large,
+}
+
+impl Cave {
+    fn of(name: &str) -> Self {
+        if name.to_lowercase() != name {
+            Cave::Large
+        } else {
+            Cave::Small
+        }
+    }
+}
+
+type Caves<'a> = Graph<&'a str, Cave>;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_paths(&cave_graph(input)?, 0)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(count_paths(&cave_graph(input)?, 1)))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.lines().all(|line| {
+            line.trim().split_once('-').is_some_and(|(src, dst)| {
+                src.chars().all(char::is_alphabetic) && dst.chars().all(char::is_alphabetic)
+            })
+        })
+    }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant::new(1, "path_traverse_once", |input, _config| {
+                Ok(Answer::from(path_traverse_once(input, START).len()))
+            }),
+            Variant::new(2, "path_traverse", |input, _config| {
+                Ok(Answer::from(path_traverse(input, START, 2).len()))
+            }),
+        ]
+    }
+
+    fn examples(&self) -> Vec<&'static str> {
+        SAMPLES.to_vec()
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        // two connected large caves would loop forever
+        let small = ["a", "b", "c", "d"];
+        let large = ["A", "B"];
+        let caves = [START, END]
+            .iter()
+            .chain(&small[..rng.range(1, 5) as usize])
+            .chain(&large[..rng.range(0, 3) as usize])
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut edges = Vec::<(&str, &str)>::new();
+        for _ in 0..rng.range(2, 10) {
+            let src = caves[rng.index(caves.len())];
+            let dst = caves[rng.index(caves.len())];
+            let is_large = |cave: &str| cave.to_lowercase() != cave;
+            if src == dst
+                || (is_large(src) && is_large(dst))
+                || edges.contains(&(src, dst))
+                || edges.contains(&(dst, src))
+            {
+                continue;
+            }
+            edges.push((src, dst));
+        }
+
+        let lines = edges.iter().map(|(src, dst)| format!("{}-{}", src, dst));
+        Some(lines.collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Directed edges, both directions except for the start and the end
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        Ok(serde_json::json!(graph_edges(&cave_graph(input)?)))
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        vec![
+            Command::new(
+                "cave_edges",
+                "",
+                "directed cave edges",
+                |input, _args, _config| {
+                    let caves = cave_graph(input).map_err(|err| err.to_string())?;
+                    Ok(graph_edges(&caves)
+                        .iter()
+                        .map(|[src, dst]| format!("{} -> {}", src, dst))
+                        .collect::<Vec<_>>()
+                        .join("\n"))
+                },
+            ),
+            Command::new(
+                "paths",
+                "<small_cave_visits>",
+                "all paths from start to end",
+                |input, args, _config| {
+                    let visits: usize = repl::arg(args, 0, "small_cave_visits")?;
+                    if visits == 0 {
+                        return Err("<small_cave_visits> must be at least 1".to_string());
+                    }
+                    let caves = cave_graph(input).map_err(|err| err.to_string())?;
+                    Ok(cave_paths(&caves, visits - 1).join("\n"))
+                },
+            ),
+        ]
+    }
+}
+
+/// Caves with directed edges, nothing leads back to the start or away from
+/// the end
+fn cave_graph(input: &str) -> Result<Caves<'_>, ParseError> {
+    let mut caves = Caves::new();
+    for (a, b) in Parser::new(input).each_line(|line| Ok((line.until("-")?, line.word()?)))? {
+        let a = caves.add_node_with(a, Cave::of(a));
+        let b = caves.add_node_with(b, Cave::of(b));
+        for (src, dst) in [(a, b), (b, a)] {
+            if *caves.key(dst) != START && *caves.key(src) != END {
+                caves.add_edge(src, dst, 1);
+            }
+        }
+    }
+    Ok(caves)
+}
+
+fn graph_edges<'a>(caves: &Caves<'a>) -> Vec<[&'a str; 2]> {
+    caves
+        .ids()
+        .flat_map(|src| {
+            caves
+                .neighbours(src)
+                .map(move |dst| [*caves.key(src), *caves.key(dst)])
+        })
+        .collect()
+}
+
+/// Paths from the start to the end, small caves are entered once except
+/// for `revisits` more visits
+fn count_paths(caves: &Caves, revisits: usize) -> u64 {
+    match (caves.id(&START), caves.id(&END)) {
+        (Some(start), Some(end)) => {
+            caves.count_paths(start, end, revisits, |id| *caves.attr(id) == Cave::Small)
+        }
+        _ => 0,
+    }
+}
+
+fn cave_paths(caves: &Caves, revisits: usize) -> Vec<String> {
+    let (Some(start), Some(end)) = (caves.id(&START), caves.id(&END)) else {
+        return Vec::new();
+    };
+    caves
+        .paths(start, end, revisits, |id| *caves.attr(id) == Cave::Small)
+        .iter()
+        .map(|path| {
+            path.iter()
+                .map(|&id| *caves.key(id))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect()
+}
+
+/// Directed edges like [`cave_graph`], for the string based traversals
+fn cave_edges(input: &str) -> Vec<[&str; 2]> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+
+            let s = line.split_once('-').unwrap();
+            let src = s.0;
+            let dst = s.1;
+
+            if dst == START || src == END {
+                Some(vec![[dst, src]])
+            } else if src != START && dst != END {
+                Some(vec![[src, dst], [dst, src]])
+            } else {
+                Some(vec![[src, dst]])
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+// the old part one variant, small caves are visited at most once
+fn path_traverse_once(input: &str, lookup_src: &str) -> Vec<String> {
+    let mut paths = Vec::<String>::new();
+    let cur_cave_src = lookup_src.split(',').next_back().unwrap();
+
+    cave_edges(input).iter().for_each(|&[src, dst]| {
+        let is_allowed = dst.to_lowercase() != dst
+            || !(lookup_src.ends_with(&(",".to_string() + dst))
+                || lookup_src.contains(&(",".to_string() + dst + ",")));
+
+        if cur_cave_src != END && src == cur_cave_src && is_allowed {
+            if dst == END {
+                paths.push(lookup_src.to_string() + "," + END);
+            } else {
+                let dst_lookup = lookup_src.to_string() + "," + dst;
+                paths.extend(path_traverse_once(input, &dst_lookup));
+            }
+        }
+    });
+
+    paths
+}
+
+fn path_traverse(input: &str, lookup_src: &str, small_cave_visits: usize) -> Vec<String> {
+    let mut paths = Vec::<String>::new();
+
+    // part 2 variant with counting small caves - slows it down
+    let mut small_caves_set = HashSet::<&str>::new();
+    let mut small_cave = None;
+    let mut cur_small_cave_visits = 0;
+    cur_small_cave_visits += lookup_src
+        .split(',')
+        .filter(|&cave| {
+            if cave != START && cave.to_lowercase() == cave {
+                if let Some(small_cave) = small_cave {
+                    cave == small_cave || !small_caves_set.insert(cave)
+                } else if !small_caves_set.insert(cave) {
+                    small_cave.replace(cave);
+                    cur_small_cave_visits += 1; // add the last one
+                    true // count more than 1 visits
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        })
+        .count();
+
+    let is_allowed = cur_small_cave_visits <= small_cave_visits;
+    trace!(path = lookup_src, is_allowed, "traverse");
+
+    if is_allowed {
+        cave_edges(input).iter().for_each(|&[src, dst]| {
+            let cur_cave_src = lookup_src.split(',').next_back().unwrap();
+
+            if cur_cave_src != END && src == cur_cave_src && is_allowed {
+                if dst == END {
+                    paths.push(lookup_src.to_string() + "," + END);
+                } else {
+                    let dst_lookup = lookup_src.to_string() + "," + dst;
+                    paths.extend(path_traverse(input, &dst_lookup, small_cave_visits));
+                }
+            }
+        });
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{diff, input::stored_input};
+
+    use super::*;
+
+    const RESULTS: &[&str] = &[
+        r"start,A,b,A,c,A,end
+          start,A,b,A,end
+          start,A,b,end
+          start,A,c,A,b,A,end
+          start,A,c,A,b,end
+          start,A,c,A,end
+          start,A,end
+          start,b,A,c,A,end
+          start,b,A,end
+          start,b,end
+         ",
+        r"start,HN,dc,HN,end
+          start,HN,dc,HN,kj,HN,end
+          start,HN,dc,end
+          start,HN,dc,kj,HN,end
+          start,HN,end
+          start,HN,kj,HN,dc,HN,end
+          start,HN,kj,HN,dc,end
+          start,HN,kj,HN,end
+          start,HN,kj,dc,HN,end
+          start,HN,kj,dc,end
+          start,dc,HN,end
+          start,dc,HN,kj,HN,end
+          start,dc,end
+          start,dc,kj,HN,end
+          start,kj,HN,dc,HN,end
+          start,kj,HN,dc,end
+          start,kj,HN,end
+          start,kj,dc,HN,end
+          start,kj,dc,end
+         ",
+        r"226",
+    ];
+
+    const RESULTS_2: &[&str] = &[
+        r"start,A,b,A,b,A,c,A,end
+          start,A,b,A,b,A,end
+          start,A,b,A,b,end
+          start,A,b,A,c,A,b,A,end
+          start,A,b,A,c,A,b,end
+          start,A,b,A,c,A,c,A,end
+          start,A,b,A,c,A,end
+          start,A,b,A,end
+          start,A,b,d,b,A,c,A,end
+          start,A,b,d,b,A,end
+          start,A,b,d,b,end
+          start,A,b,end
+          start,A,c,A,b,A,b,A,end
+          start,A,c,A,b,A,b,end
+          start,A,c,A,b,A,c,A,end
+          start,A,c,A,b,A,end
+          start,A,c,A,b,d,b,A,end
+          start,A,c,A,b,d,b,end
+          start,A,c,A,b,end
+          start,A,c,A,c,A,b,A,end
+          start,A,c,A,c,A,b,end
+          start,A,c,A,c,A,end
+          start,A,c,A,end
+          start,A,end
+          start,b,A,b,A,c,A,end
+          start,b,A,b,A,end
+          start,b,A,b,end
+          start,b,A,c,A,b,A,end
+          start,b,A,c,A,b,end
+          start,b,A,c,A,c,A,end
+          start,b,A,c,A,end
+          start,b,A,end
+          start,b,d,b,A,c,A,end
+          start,b,d,b,A,end
+          start,b,d,b,end
+          start,b,end
+        ",
+        "103",
+        "3509",
+    ];
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 12).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_path_traverse() {
+        (0..=2usize).for_each(|n| {
+            let input = SAMPLES[n];
+            let result = RESULTS[n];
+
+            let result = if let Ok(result_len) = result.parse::<usize>() {
+                let mut v = Vec::new();
+                v.resize(result_len, "");
+                v
+            } else {
+                result
+                    .lines()
+                    .filter_map(|line| {
+                        let line = line.trim();
+                        if line.is_empty() {
+                            None
+                        } else {
+                            Some(line)
+                        }
+                    })
+                    .collect::<Vec<&str>>()
+            };
+
+            let caves = cave_graph(input).unwrap();
+            assert_eq!(cave_paths(&caves, 0).len(), result.len());
+            assert_eq!(count_paths(&caves, 0), result.len() as u64);
+            assert_eq!(path_traverse(input, START, 1).len(), result.len());
+        });
+    }
+
+    #[test]
+    fn test_path_traverse_2() {
+        (0..=2usize).for_each(|n| {
+            let input = SAMPLES[n];
+            let result = RESULTS_2[n];
+
+            let result = if let Ok(result_len) = result.parse::<usize>() {
+                let mut v = Vec::new();
+                v.resize(result_len, "");
+                v
+            } else {
+                result
+                    .lines()
+                    .filter_map(|line| {
+                        let line = line.trim();
+                        if line.is_empty() {
+                            None
+                        } else {
+                            Some(line)
+                        }
+                    })
+                    .collect::<Vec<&str>>()
+            };
+
+            let caves = cave_graph(input).unwrap();
+            assert_eq!(cave_paths(&caves, 1).len(), result.len());
+            assert_eq!(count_paths(&caves, 1), result.len() as u64);
+        });
+    }
+
+    #[test]
+    fn test_cave_graph() {
+        let caves = cave_graph(SAMPLES[0]).unwrap();
+        assert_eq!(caves.len(), 6);
+        assert_eq!(caves.attr(caves.id(&"A").unwrap()), &Cave::Large);
+        assert_eq!(
+            &graph_edges(&caves)[..3],
+            [["start", "A"], ["start", "b"], ["A", "c"]]
+        );
+
+        assert_eq!(
+            cave_graph("start-A\nA end\n").unwrap_err().to_string(),
+            "line 2, column 1: expected '-', found 'A'"
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 12);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 3369);
+        assert_eq!(Day.part2(&input, &config).unwrap(), 85883);
+    }
+
+    #[test]
+    fn test_variants() {
+        let config = Config::new(2021, 12);
+        match diff::check(&Day, &config, 200, 0) {
+            Ok(summary) => assert_eq!(summary.inputs, 203),
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
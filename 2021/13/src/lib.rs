@@ -0,0 +1,302 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{
+    parse::{ParseError, Parser},
+    Answer, Config, Grid, Solution, SolveError,
+};
+use tracing::debug;
+
+const FOLD_HORIZONTAL: char = 'x';
+const FOLD_VERTICAL: char = 'y';
+
+/// Dots as `dots[y][x]`
+type Dots = Vec<Vec<bool>>;
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let (mut dots, folds) = parse_data(input)?;
+        let fold_sample = *folds
+            .first()
+            .ok_or_else(|| SolveError::Parse("no fold instructions".to_string()))?;
+
+        fold(&mut dots, fold_sample);
+        Ok(Answer::from(count_dots(&dots)))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        let (mut dots, folds) = parse_data(input)?;
+        folds
+            .iter()
+            .for_each(|&fold_instr| fold(&mut dots, fold_instr));
+
+        Ok(Answer::from(Grid::from_cells(&dots, 'X', ' ')))
+    }
+
+    /// Dots as `[x, y]` and the folds as `["x", 655]`
+    fn parse(&self, input: &str) -> Result<serde_json::Value, SolveError> {
+        let (dots, folds) = parse_data(input)?;
+        let dots = dots
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &dot)| dot)
+                    .map(move |(x, _)| [x, y])
+            })
+            .collect::<Vec<_>>();
+        let folds = folds
+            .iter()
+            .map(|&(dir, pos)| serde_json::json!([dir.to_string(), pos]))
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!({ "dots": dots, "folds": folds }))
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        input.contains("fold along")
+    }
+}
+
+/// The dots and the folds, two blocks separated by a blank line
+fn parse_data(input: &str) -> Result<(Dots, Vec<(char, u16)>), ParseError> {
+    let mut records = Parser::new(input).records();
+    let mut dots: Dots = Vec::new();
+
+    if let Some(record) = records.next() {
+        for (x, y) in record.each_line(|line| line.pair::<usize>(','))? {
+            while dots.len() <= y {
+                dots.push(Vec::new());
+            }
+            while dots[y].len() <= x {
+                dots[y].push(false);
+            }
+
+            // mark dot
+            dots[y][x] = true;
+        }
+    }
+
+    // the folds are missing in some examples
+    let folds = match records.next() {
+        Some(record) => record.each_line(|line| {
+            line.tag("fold along")?;
+            let at_direction = *line;
+            let direction = line.char()?;
+            if direction != FOLD_HORIZONTAL && direction != FOLD_VERTICAL {
+                return Err(at_direction.error(format!(
+                    "expected '{}' or '{}', found '{}'",
+                    FOLD_HORIZONTAL, FOLD_VERTICAL, direction
+                )));
+            }
+            line.tag("=")?;
+            Ok((direction, line.number()?))
+        })?,
+        None => Vec::new(),
+    };
+
+    match records.next() {
+        Some(record) => Err(record.error("expected dots and folds only")),
+        None => Ok((dots, folds)),
+    }
+}
+
+fn fold(dots: &mut Vec<Vec<bool>>, fold: (char, u16)) {
+    let fold_dir = fold.0;
+    let fold_pos = fold.1 as usize;
+    debug!(direction = %fold_dir, position = fold_pos, "fold");
+
+    if fold_dir == FOLD_VERTICAL {
+        ((fold_pos + 1)..dots.len()).for_each(|y| {
+            (0..dots[y].len()).for_each(|x| {
+                if dots[y][x] {
+                    let y = fold_pos - (y - fold_pos);
+                    while dots[y].len() <= x {
+                        dots[y].push(false);
+                    }
+                    dots[y][x] = true;
+                }
+            });
+        });
+        dots.truncate(fold_pos);
+    } else if fold_dir == FOLD_HORIZONTAL {
+        (0..dots.len()).for_each(|y| {
+            let dots_line = &mut dots[y];
+            if dots_line.len() > fold_pos {
+                (fold_pos..dots_line.len()).for_each(|x| {
+                    if dots_line[x] {
+                        let x = fold_pos - (x - fold_pos);
+                        dots_line[x] = true;
+                    }
+                });
+            }
+            dots_line.truncate(fold_pos);
+        });
+    }
+}
+
+#[inline(always)]
+fn count_dots(dots: &[Vec<bool>]) -> usize {
+    dots.iter()
+        .map(|dots_line| dots_line.iter().filter(|&is_dot| *is_dot).count())
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+
+    use super::*;
+
+    const TEST_INPUT: &str = r"6,10
+                               0,14
+                               9,10
+                               0,3
+                               10,4
+                               4,11
+                               6,0
+                               6,12
+                               4,1
+                               0,13
+                               10,12
+                               3,4
+                               3,0
+                               8,4
+                               1,10
+                               2,14
+                               8,10
+                               9,0
+                               
+                               fold along y=7
+                               fold along x=5
+                              ";
+
+    #[test]
+    fn test_get_input() {
+        let Some(input) = stored_input(&Config::new(2021, 13).input_path()) else {
+            return;
+        };
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let input = TEST_INPUT;
+
+        let data = parse_data(input).unwrap();
+        let dots = data.0;
+
+        assert!(dots[0][3]);
+        assert!(dots[1][4]);
+        assert!(dots[12][10]);
+        assert!(dots[14][0]);
+        assert_eq!(dots[2].len(), 0);
+        assert_eq!(dots[11].len(), 5);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_data("6,10\n0,14\n\nfold along z=7")
+                .unwrap_err()
+                .to_string(),
+            "line 4, column 12: expected 'x' or 'y', found 'z'"
+        );
+        assert_eq!(
+            parse_data("6,10\n0 14\n").unwrap_err().to_string(),
+            "line 2, column 3: expected ',', found '14'"
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        let input = TEST_INPUT;
+
+        let data = parse_data(input).unwrap();
+        let mut dots = data.0;
+        let folds = data.1;
+
+        let fold_sample = folds[0];
+
+        fold(&mut dots, fold_sample);
+
+        assert!(dots[0][3]);
+        assert!(dots[1][4]);
+        if fold_sample.0 == FOLD_VERTICAL {
+            assert_eq!(dots.len(), fold_sample.1 as usize);
+        } else if fold_sample.0 == FOLD_HORIZONTAL {
+            assert!(dots[0].len() <= fold_sample.1 as usize);
+        }
+
+        assert_eq!(dots[2].len(), 11);
+        assert_eq!(dots[3].len(), 5);
+        assert!(dots[3][0]);
+        assert!(!dots[3][3]);
+        assert!(dots[3][4]);
+
+        let fold_sample = folds[1];
+
+        fold(&mut dots, fold_sample);
+
+        if fold_sample.0 == FOLD_VERTICAL {
+            assert_eq!(dots.len(), fold_sample.1 as usize);
+        } else if fold_sample.0 == FOLD_HORIZONTAL {
+            assert!(dots[0].len() == fold_sample.1 as usize);
+        }
+
+        (0..5).for_each(|x| assert!(dots[0][x]));
+        (0..5).for_each(|x| assert!(dots[4][x]));
+    }
+
+    #[test]
+    fn test_count_dots() {
+        let input = TEST_INPUT;
+
+        let data = parse_data(input).unwrap();
+        let mut dots = data.0;
+        let folds = data.1;
+
+        let fold_sample = folds[0];
+
+        fold(&mut dots, fold_sample);
+        assert_eq!(count_dots(&dots), 17);
+    }
+
+    #[test]
+    fn test_solution() {
+        let config = Config::new(2021, 13);
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        assert_eq!(Day.part1(&input, &config).unwrap(), 710);
+        let code = Day.part2(&input, &config).unwrap();
+        match &code {
+            Answer::Grid(grid) => assert_eq!(grid.rows().len(), 6),
+            answer => panic!("expected a grid, got {}", answer),
+        }
+        assert!(code.to_string().starts_with("XXXX XXX  X     XX  XXX"));
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let config = Config::new(2021, 13);
+        let example = Day.part2(TEST_INPUT, &config).unwrap();
+        aoc_common::assert_snapshot!("example_part2", example);
+
+        let Some(input) = stored_input(&config.input_path()) else {
+            return;
+        };
+        aoc_common::assert_snapshot!("part2", Day.part2(&input, &config).unwrap());
+    }
+}
@@ -0,0 +1,45 @@
+//! 2015/01 as a plugin, part 1 starts at the parameter `floor`
+
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+use aoc_common::{Answer, Config, Solution, SolveError};
+
+pub struct Day;
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        2015
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let floor = moves(input).sum::<i64>();
+        Ok(Answer::from(config.param_or("floor", 0i64)? + floor))
+    }
+
+    fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        moves(input)
+            .scan(0, |floor, step| {
+                *floor += step;
+                Some(*floor)
+            })
+            .position(|floor| floor < 0)
+            .map(|position| Answer::from(position + 1))
+            .ok_or_else(|| SolveError::NoSolution("never in the basement".to_string()))
+    }
+}
+
+fn moves(input: &str) -> impl Iterator<Item = i64> + '_ {
+    input.trim().chars().map(|c| match c {
+        '(' => 1,
+        ')' => -1,
+        _ => panic!("invalid move '{}'", c),
+    })
+}
+
+aoc_common::export_plugin!(Day);
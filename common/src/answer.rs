@@ -0,0 +1,418 @@
+//! Puzzle answers of all shapes behind one type.
+//!
+//! Numeric answers compare by value regardless of the variant, so
+//! `Answer::Unsigned(5) == Answer::Signed(5)`. Serialized, integers which fit
+//! into an `i64` are numbers, larger ones strings of digits, text is
+//! `{"text": ...}` and a grid `{"grid": [rows]}`, so every answer comes back
+//! as it was. Plain strings, as in older stored answers, are read as numbers
+//! when they are one exactly, as grids with several lines and as text
+//! otherwise.
+
+use std::{cmp::Ordering, fmt, hash, str::FromStr};
+
+use num_bigint::BigInt;
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+    Grid(Grid),
+}
+
+/// Rendered rows of a picture answer, e.g. letters drawn by dots
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Grid {
+    rows: Vec<String>,
+}
+
+impl Grid {
+    pub fn new(rows: Vec<String>) -> Self {
+        Self { rows }
+    }
+
+    pub fn from_cells<R: AsRef<[bool]>>(cells: &[R], on: char, off: char) -> Self {
+        let rows = cells
+            .iter()
+            .map(|row| {
+                row.as_ref()
+                    .iter()
+                    .map(|&is_on| if is_on { on } else { off })
+                    .collect()
+            })
+            .collect();
+        Self { rows }
+    }
+
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.rows.join("\n"))
+    }
+}
+
+// sort order between different kinds of answers
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Key<'a> {
+    Number(BigInt),
+    Text(&'a str),
+    Grid(&'a Grid),
+}
+
+impl Answer {
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Answer::Signed(_) | Answer::Unsigned(_) | Answer::Big(_)
+        )
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+
+    fn key(&self) -> Key<'_> {
+        match self {
+            Answer::Text(s) => Key::Text(s),
+            Answer::Grid(grid) => Key::Grid(grid),
+            _ => Key::Number(self.to_bigint().unwrap()),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Grid(grid) => write!(f, "{}", grid),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Answer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl hash::Hash for Answer {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Integers become numbers, several lines a grid and anything else text
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(n) = s.parse::<u128>() {
+            Ok(Answer::Unsigned(n))
+        } else if let Ok(n) = s.parse::<i128>() {
+            Ok(Answer::Signed(n))
+        } else if let Ok(n) = s.parse::<BigInt>() {
+            Ok(Answer::Big(n))
+        } else if s.contains('\n') {
+            Ok(Answer::Grid(Grid::new(
+                s.lines().map(str::to_string).collect(),
+            )))
+        } else {
+            Ok(Answer::Text(s.to_string()))
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+
+            impl PartialEq<$t> for Answer {
+                fn eq(&self, other: &$t) -> bool {
+                    let other = Answer::from(*other);
+                    *self == other
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+answer_from_int!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<Grid> for Answer {
+    fn from(grid: Grid) -> Self {
+        Answer::Grid(grid)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(s) => s == other,
+            answer => other.parse::<Answer>().is_ok_and(|other| *answer == other),
+        }
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            // TOML integers are signed
+            Answer::Unsigned(n) if i64::try_from(*n).is_ok() => serializer.serialize_u64(*n as u64),
+            Answer::Signed(n) if i64::try_from(*n).is_ok() => serializer.serialize_i64(*n as i64),
+            Answer::Text(text) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("text", text)?;
+                map.end()
+            }
+            Answer::Grid(grid) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("grid", grid.rows())?;
+                map.end()
+            }
+            answer => serializer.serialize_str(&answer.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> de::Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "an integer, a string, {{\"text\": ...}} or {{\"grid\": [...]}}"
+                )
+            }
+
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+                // "0123" or "+5" would change when written back
+                match s.parse::<BigInt>() {
+                    Ok(n) if n.to_string() == s => Ok(s.parse().unwrap()),
+                    _ if s.contains('\n') => Ok(Answer::Grid(Grid::new(
+                        s.lines().map(str::to_string).collect(),
+                    ))),
+                    _ => Ok(Answer::Text(s.to_string())),
+                }
+            }
+
+            fn visit_map<M: de::MapAccess<'de>>(self, mut map: M) -> Result<Answer, M::Error> {
+                let answer = match map.next_key::<String>()?.as_deref() {
+                    Some("text") => Answer::Text(map.next_value()?),
+                    Some("grid") => Answer::Grid(Grid::new(map.next_value()?)),
+                    Some(key) => return Err(de::Error::unknown_field(key, &["text", "grid"])),
+                    None => return Err(de::Error::invalid_length(0, &self)),
+                };
+                if map.next_key::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(2, &self));
+                }
+                Ok(answer)
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_eq() {
+        assert_eq!(Answer::from(5u8), Answer::from(5i64));
+        assert_eq!(Answer::from(5u16), Answer::Big(BigInt::from(5)));
+        assert_ne!(Answer::from(5u16), Answer::from(-5i16));
+        assert_ne!(Answer::from(5u16), Answer::from("5"));
+        assert_eq!(Answer::from(26984457539u64), 26984457539usize);
+        assert_eq!(Answer::from(-1i32), -1);
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut answers = [
+            Answer::from("abc"),
+            Answer::from(10u32),
+            Answer::from(-3i32),
+            Answer::Big(BigInt::from(u128::MAX) * 2),
+        ];
+        answers.sort();
+        assert_eq!(answers[0], -3);
+        assert_eq!(answers[1], 10);
+        assert!(answers[2].is_numeric());
+        assert_eq!(answers[3], "abc");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(514579u32).to_string(), "514579");
+        assert_eq!(Answer::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(Answer::from("EFJKZLBL").to_string(), "EFJKZLBL");
+
+        let grid = Grid::from_cells(&[vec![true, false, true], vec![false, true]], '#', '.');
+        assert_eq!(Answer::from(grid).to_string(), "#.#\n.#");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("1632146183902".parse::<Answer>().unwrap(), 1632146183902u64);
+        assert_eq!("-42".parse::<Answer>().unwrap(), -42);
+
+        let big = "340282366920938463463374607431768211456"
+            .parse::<Answer>()
+            .unwrap();
+        assert!(matches!(big, Answer::Big(_)));
+
+        let grid = "#.\n.#".parse::<Answer>().unwrap();
+        assert!(matches!(grid, Answer::Grid(_)));
+        assert_eq!(grid.to_string(), "#.\n.#");
+
+        assert!(matches!(
+            "start,A,end".parse::<Answer>().unwrap(),
+            Answer::Text(_)
+        ));
+    }
+
+    #[test]
+    fn test_serde() {
+        let answers = vec![
+            Answer::from(7686u32),
+            Answer::from(-3i8),
+            Answer::from(u128::MAX),
+            Answer::from("EFJKZLBL"),
+            Answer::from(Grid::new(vec!["X ".to_string(), " X".to_string()])),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r#"[7686,-3,"340282366920938463463374607431768211455",{"text":"EFJKZLBL"},{"grid":["X "," X"]}]"#
+        );
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+
+        assert!(serde_json::from_str::<Answer>(r#"{"list":[]}"#).is_err());
+        assert!(serde_json::from_str::<Answer>(r#"{"text":"a","grid":[]}"#).is_err());
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let answers = [
+            Answer::Unsigned(0),
+            Answer::Unsigned(u64::MAX.into()),
+            Answer::Unsigned(u128::MAX),
+            Answer::Signed(-1),
+            Answer::Signed(i64::MIN.into()),
+            Answer::Signed(i128::MIN),
+            Answer::Big(BigInt::from(u128::MAX) * -7),
+            Answer::from("0123"),
+            Answer::from("123"),
+            Answer::from("-5"),
+            Answer::from(""),
+            Answer::from("line\nbreak"),
+            Answer::from(Grid::new(vec!["#.#".to_string()])),
+            Answer::from(Grid::new(Vec::new())),
+            Answer::from(Grid::new(vec!["X ".to_string(), " X".to_string()])),
+        ];
+        for answer in answers {
+            let json = serde_json::to_string(&answer).unwrap();
+            let parsed = serde_json::from_str::<Answer>(&json).unwrap();
+            assert_eq!(parsed, answer, "{}", json);
+            // the same kind, not just an equal value
+            assert_eq!(
+                std::mem::discriminant(&parsed.key()),
+                std::mem::discriminant(&answer.key())
+            );
+
+            #[derive(Serialize, Deserialize)]
+            struct Stored {
+                part1: Answer,
+            }
+            let toml = toml::to_string(&Stored {
+                part1: answer.clone(),
+            })
+            .unwrap();
+            assert_eq!(
+                toml::from_str::<Stored>(&toml).unwrap().part1,
+                answer,
+                "{}",
+                toml
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_plain_strings() {
+        let parse = |json: &str| serde_json::from_str::<Answer>(json).unwrap();
+        assert!(matches!(parse(r#""123""#), Answer::Unsigned(123)));
+        assert!(matches!(parse(r#""-123""#), Answer::Signed(-123)));
+        assert!(matches!(parse(r#""0123""#), Answer::Text(_)));
+        assert!(matches!(parse(r#""+5""#), Answer::Text(_)));
+        assert!(matches!(parse(r#""EFJKZLBL""#), Answer::Text(_)));
+        assert_eq!(parse(r#""X \n X""#).to_string(), "X \n X");
+        assert!(matches!(parse(r#""X \n X""#), Answer::Grid(_)));
+    }
+}
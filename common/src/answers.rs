@@ -0,0 +1,116 @@
+//! Answers stored next to an input, with a fingerprint of that input.
+//!
+//! The fingerprint is the SHA-256 of the input file, so answers recorded for
+//! one account's input are not silently compared against another's.
+
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{answer::Answer, config::ConfigError};
+
+/// Hex SHA-256 of the input contents
+pub fn fingerprint(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StoredAnswers {
+    pub input_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+}
+
+impl StoredAnswers {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input_sha256: fingerprint(input),
+            ..Self::default()
+        }
+    }
+
+    /// `None` if no answers were recorded yet
+    pub fn load(path: &Path) -> Result<Option<Self>, ConfigError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(ConfigError::Io(path.to_path_buf(), err)),
+        };
+        toml::from_str(&contents)
+            .map(Some)
+            .map_err(|err| ConfigError::Toml(path.to_path_buf(), Box::new(err)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        let contents = toml::to_string(self)
+            .map_err(|err| ConfigError::Invalid(format!("{}: {}", path.display(), err)))?;
+        fs::write(path, contents).map_err(|err| ConfigError::Io(path.to_path_buf(), err))
+    }
+
+    pub fn is_for(&self, input: &str) -> bool {
+        self.input_sha256 == fingerprint(input)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: Answer) {
+        match part {
+            1 => self.part1 = Some(answer),
+            2 => self.part2 = Some(answer),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(
+            fingerprint(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(fingerprint("3,4,3,1,2"), fingerprint("3,4,3,1,2\n"));
+    }
+
+    #[test]
+    fn test_toml() {
+        let mut stored = StoredAnswers::new("3,4,3,1,2");
+        stored.set(1, Answer::from(5934u32));
+        stored.set(2, Answer::from(26984457539u64));
+        assert!(stored.is_for("3,4,3,1,2"));
+        assert!(!stored.is_for("3,4,3,1,2,1"));
+
+        let contents = toml::to_string(&stored).unwrap();
+        assert!(contents.contains("part1 = 5934\n"));
+        assert_eq!(toml::from_str::<StoredAnswers>(&contents).unwrap(), stored);
+
+        let partial: StoredAnswers = toml::from_str(r#"input_sha256 = "ab""#).unwrap();
+        assert_eq!(partial.get(1), None);
+    }
+
+    #[test]
+    fn test_grid_answer() {
+        let mut stored = StoredAnswers::new("");
+        stored.set(2, "X  X\nXXXX".parse().unwrap());
+        let contents = toml::to_string(&stored).unwrap();
+        let loaded = toml::from_str::<StoredAnswers>(&contents).unwrap();
+        assert_eq!(loaded.get(2).unwrap().to_string(), "X  X\nXXXX");
+        assert!(matches!(loaded.get(2), Some(Answer::Grid(_))));
+    }
+}
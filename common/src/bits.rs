@@ -0,0 +1,476 @@
+//! Sets of small integers as bits: marks on bingo boards, present passport
+//! fields, columns of binary numbers.
+//!
+//! [`BitSet`] grows as bits are inserted, [`FixedBitSet`] has a capacity
+//! known at compile time and lives on the stack. [`Flags`] is a mask of the
+//! variants of an enum implementing [`Flag`] and [`column_counts`] counts
+//! every bit position across many words.
+//!
+//! ```
+//! use aoc_common::bits::{self, BitSet};
+//!
+//! let mut marks = BitSet::new();
+//! marks.insert(3);
+//! marks.insert(130);
+//! assert_eq!(marks.iter().collect::<Vec<_>>(), [3, 130]);
+//! assert_eq!(marks.len(), 2);
+//!
+//! // how often bits 0 to 3 are set
+//! assert_eq!(bits::column_counts([0b0110, 0b0011], 4), [1, 2, 1, 0]);
+//! ```
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Set of `usize` stored as bits in the words `S`, a `Vec<u64>` that grows
+/// or a fixed array. Sets with the same bits are equal whatever their
+/// capacity.
+#[derive(Clone, Default)]
+pub struct BitSet<S = Vec<u64>> {
+    words: S,
+}
+
+/// A [`BitSet`] of `0..N * 64`
+pub type FixedBitSet<const N: usize> = BitSet<[u64; N]>;
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    /// Room for `0..bits` without growing
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: vec![0; bits.div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// `true` if `bit` wasn't in the set
+    pub fn insert(&mut self, bit: usize) -> bool {
+        let word = bit / WORD_BITS;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.set(bit)
+    }
+
+    /// Adds the bits of `other`, growing as needed
+    pub fn union_with<T: AsRef<[u64]>>(&mut self, other: &BitSet<T>) {
+        let other = other.significant_words();
+        if other.len() > self.words.len() {
+            self.words.resize(other.len(), 0);
+        }
+        self.or_words(other);
+    }
+}
+
+impl<const N: usize> FixedBitSet<N> {
+    pub const fn new_fixed() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// `true` if `bit` wasn't in the set, panics beyond the capacity
+    pub fn insert(&mut self, bit: usize) -> bool {
+        assert!(
+            bit < N * WORD_BITS,
+            "bit {} beyond the capacity {}",
+            bit,
+            N * WORD_BITS
+        );
+        self.set(bit)
+    }
+
+    /// Adds the bits of `other` that fit
+    pub fn union_with<T: AsRef<[u64]>>(&mut self, other: &BitSet<T>) {
+        self.or_words(other.words.as_ref());
+    }
+}
+
+impl<S: AsRef<[u64]>> BitSet<S> {
+    pub fn contains(&self, bit: usize) -> bool {
+        self.words
+            .as_ref()
+            .get(bit / WORD_BITS)
+            .is_some_and(|word| word & 1 << (bit % WORD_BITS) != 0)
+    }
+
+    /// Number of bits in the set
+    pub fn len(&self) -> usize {
+        self.words
+            .as_ref()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.as_ref().iter().all(|&word| word == 0)
+    }
+
+    /// The bits in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .as_ref()
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, &word)| Ones(word).map(move |bit| idx * WORD_BITS + bit))
+    }
+
+    // the words without the trailing empty ones
+    fn significant_words(&self) -> &[u64] {
+        let words = self.words.as_ref();
+        let len = words
+            .iter()
+            .rposition(|&word| word != 0)
+            .map_or(0, |idx| idx + 1);
+        &words[..len]
+    }
+
+    /// Every bit of `other` is in the set
+    pub fn is_superset<T: AsRef<[u64]>>(&self, other: &BitSet<T>) -> bool {
+        let words = self.words.as_ref();
+        other
+            .words
+            .as_ref()
+            .iter()
+            .enumerate()
+            .all(|(idx, &word)| word & !words.get(idx).copied().unwrap_or(0) == 0)
+    }
+}
+
+impl<S: AsRef<[u64]> + AsMut<[u64]>> BitSet<S> {
+    /// `true` if `bit` was in the set
+    pub fn remove(&mut self, bit: usize) -> bool {
+        match self.words.as_mut().get_mut(bit / WORD_BITS) {
+            Some(word) => {
+                let mask = 1 << (bit % WORD_BITS);
+                let was_set = *word & mask != 0;
+                *word &= !mask;
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.as_mut().fill(0);
+    }
+
+    /// Keeps only the bits also in `other`
+    pub fn intersect_with<T: AsRef<[u64]>>(&mut self, other: &BitSet<T>) {
+        let other = other.words.as_ref();
+        for (idx, word) in self.words.as_mut().iter_mut().enumerate() {
+            *word &= other.get(idx).copied().unwrap_or(0);
+        }
+    }
+
+    fn or_words(&mut self, other: &[u64]) {
+        for (word, other) in self.words.as_mut().iter_mut().zip(other) {
+            *word |= other;
+        }
+    }
+
+    // the word of `bit` exists
+    fn set(&mut self, bit: usize) -> bool {
+        let word = &mut self.words.as_mut()[bit / WORD_BITS];
+        let mask = 1 << (bit % WORD_BITS);
+        let was_set = *word & mask != 0;
+        *word |= mask;
+        !was_set
+    }
+}
+
+impl<S: AsRef<[u64]>, T: AsRef<[u64]>> PartialEq<BitSet<T>> for BitSet<S> {
+    fn eq(&self, other: &BitSet<T>) -> bool {
+        self.significant_words() == other.significant_words()
+    }
+}
+
+impl<S: AsRef<[u64]>> Eq for BitSet<S> {}
+
+impl<S: AsRef<[u64]>> Hash for BitSet<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.significant_words().hash(state);
+    }
+}
+
+impl<S: AsRef<[u64]>> fmt::Debug for BitSet<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(bits: I) -> Self {
+        let mut set = BitSet::new();
+        for bit in bits {
+            set.insert(bit);
+        }
+        set
+    }
+}
+
+/// Positions of the set bits of a word, lowest first
+#[derive(Clone, Copy, Debug)]
+pub struct Ones(pub u64);
+
+impl Iterator for Ones {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+/// How many of the `words` have bit 0, 1, ... `width - 1` set
+pub fn column_counts(words: impl IntoIterator<Item = u64>, width: usize) -> Vec<usize> {
+    assert!(width <= WORD_BITS, "{} columns in a u64", width);
+    let mut counts = vec![0; width];
+    for word in words {
+        for bit in Ones(word).take_while(|&bit| bit < width) {
+            counts[bit] += 1;
+        }
+    }
+    counts
+}
+
+/// Enum with at most 64 variants, the keys of [`Flags`]
+pub trait Flag: Copy + Eq + 'static {
+    /// Every variant, in the order of their bits
+    const ALL: &'static [Self];
+
+    fn bit(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&flag| flag == self)
+            .expect("a variant missing in Flag::ALL")
+    }
+}
+
+/// Set of the variants of the enum `F`
+pub struct Flags<F> {
+    bits: u64,
+    flags: PhantomData<F>,
+}
+
+impl<F: Flag> Flags<F> {
+    pub const fn empty() -> Self {
+        Self {
+            bits: 0,
+            flags: PhantomData,
+        }
+    }
+
+    pub fn all() -> Self {
+        F::ALL.iter().copied().collect()
+    }
+
+    /// The raw mask, bit `n` is `F::ALL[n]`
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    pub fn insert(&mut self, flag: F) {
+        self.bits |= 1 << flag.bit();
+    }
+
+    pub fn remove(&mut self, flag: F) {
+        self.bits &= !(1 << flag.bit());
+    }
+
+    /// Without `flag`
+    pub fn without(mut self, flag: F) -> Self {
+        self.remove(flag);
+        self
+    }
+
+    pub fn contains(&self, flag: F) -> bool {
+        self.bits & 1 << flag.bit() != 0
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.bits & !self.bits == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = F> {
+        Ones(self.bits).map(|bit| F::ALL[bit])
+    }
+}
+
+// derives would require the bounds on `F`
+impl<F> Clone for Flags<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for Flags<F> {}
+
+impl<F> PartialEq for Flags<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<F> Eq for Flags<F> {}
+
+impl<F: Flag> Default for Flags<F> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<F: Flag + fmt::Debug> fmt::Debug for Flags<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<F: Flag> FromIterator<F> for Flags<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(flags: I) -> Self {
+        let mut set = Self::empty();
+        for flag in flags {
+            set.insert(flag);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    impl Flag for Color {
+        const ALL: &'static [Self] = &[Color::Red, Color::Green, Color::Blue];
+    }
+
+    #[test]
+    fn test_bit_set() {
+        let mut set = BitSet::with_capacity(10);
+        assert!(set.is_empty());
+        assert!(set.insert(5));
+        assert!(!set.insert(5));
+        assert!(set.insert(64));
+        assert!(set.insert(200));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(200) && !set.contains(199) && !set.contains(10_000));
+        assert!(set.remove(64));
+        assert!(!set.remove(64) && !set.remove(10_000));
+        assert_eq!(format!("{:?}", set), "{5, 200}");
+
+        let other = [5, 7].into_iter().collect::<BitSet>();
+        assert!(!set.is_superset(&other));
+        set.union_with(&other);
+        assert!(set.is_superset(&other));
+        set.intersect_with(&other);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [5, 7]);
+        set.clear();
+        assert!(set.is_empty());
+
+        // grows for the bits of `other`
+        let mut empty = BitSet::new();
+        empty.union_with(&[5, 300].into_iter().collect::<BitSet>());
+        assert_eq!(empty.iter().collect::<Vec<_>>(), [5, 300]);
+    }
+
+    #[test]
+    fn test_eq_ignores_capacity() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |set: &BitSet| {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(BitSet::with_capacity(200), BitSet::new());
+        assert_eq!(hash(&BitSet::with_capacity(200)), hash(&BitSet::new()));
+
+        let mut removed = [3, 130].into_iter().collect::<BitSet>();
+        removed.remove(130);
+        let never = [3].into_iter().collect::<BitSet>();
+        assert_eq!(removed, never);
+        assert_eq!(hash(&removed), hash(&never));
+        assert_ne!(removed, BitSet::new());
+
+        let mut fixed = FixedBitSet::<4>::new_fixed();
+        fixed.insert(3);
+        assert_eq!(fixed, never);
+    }
+
+    #[test]
+    fn test_fixed_bit_set() {
+        let mut set = FixedBitSet::<2>::new_fixed();
+        set.insert(0);
+        set.insert(127);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 127]);
+
+        let mut grown = BitSet::new();
+        grown.insert(127);
+        assert!(set.is_superset(&grown));
+        assert!(!grown.is_superset(&set));
+
+        // the bits beyond the capacity are dropped
+        grown.insert(128);
+        set.union_with(&grown);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 127]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 64 beyond the capacity 64")]
+    fn test_fixed_capacity() {
+        FixedBitSet::<1>::new_fixed().insert(64);
+    }
+
+    #[test]
+    fn test_column_counts() {
+        assert_eq!(Ones(0b1010_0001).collect::<Vec<_>>(), [0, 5, 7]);
+        assert_eq!(Ones(u64::MAX).count(), 64);
+        assert_eq!(column_counts([0b111, 0b101, 0b100], 2), [2, 1]);
+        assert_eq!(column_counts([u64::MAX], 64), vec![1; 64]);
+        assert_eq!(column_counts(Vec::new(), 3), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut flags = Flags::<Color>::empty();
+        flags.insert(Color::Blue);
+        assert_eq!(flags.bits(), 0b100);
+        assert!(flags.contains(Color::Blue) && !flags.contains(Color::Red));
+
+        let all = Flags::<Color>::all();
+        assert_eq!(all.len(), 3);
+        assert!(all.is_superset(&flags) && !flags.is_superset(&all));
+        assert_eq!(
+            all.without(Color::Green).iter().collect::<Vec<_>>(),
+            [Color::Red, Color::Blue]
+        );
+        assert_eq!(format!("{:?}", all.without(Color::Red)), "{Green, Blue}");
+    }
+}
@@ -0,0 +1,599 @@
+//! Layered configuration shared by all days.
+//!
+//! Settings are merged from (lowest to highest priority) the built-in
+//! defaults, the repository `aoc.toml`, the user file, `AOC_*` environment
+//! variables and command line flags.
+
+use std::{
+    collections::BTreeMap,
+    env, error, fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+use crate::{crypto, logging, SolveError};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+const DEFAULT_INPUT_DIR: &str = "./data";
+const DEFAULT_INPUT_FILE: &str = "input";
+const ANSWERS_SUFFIX: &str = ".answers.toml";
+/// Named inputs of several accounts, e.g. `data/inputs/alice`
+pub const USER_INPUTS_DIR: &str = "inputs";
+const DEFAULT_PARTS: &[u8] = &[1, 2];
+const ENV_PREFIX: &str = "AOC_";
+const ENV_PARAM_PREFIX: &str = "AOC_PARAM_";
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, Box<toml::de::Error>),
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Toml(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(ConfigError::Invalid(format!(
+                "unknown output format '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+/// One source of settings, every field is optional and overrides lower layers.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    pub input_dir: Option<PathBuf>,
+    pub input_file: Option<String>,
+    /// Named input in the inputs directory instead of the input file
+    pub user: Option<String>,
+    pub session_file: Option<PathBuf>,
+    pub format: Option<Format>,
+    pub parts: Option<Vec<u8>>,
+    /// `tracing` filter directives, e.g. `info` or `aoc_2021_06=trace`
+    pub log_filter: Option<String>,
+    /// Log level shortcut for `-v`, `-vv` and `-vvv` when no filter is set
+    pub verbosity: Option<u8>,
+    /// Per-day parameters from files, keyed by `"<year>/<day>"`, e.g. `"2021/04"`
+    #[serde(default)]
+    pub days: BTreeMap<String, BTreeMap<String, toml::Value>>,
+    /// Parameters for the current day (environment and command line)
+    #[serde(skip)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl Layer {
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        Self::from_toml(&contents).map_err(|err| ConfigError::Toml(path.to_path_buf(), err))
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, Box<toml::de::Error>> {
+        let mut layer: Layer = toml::from_str(contents).map_err(Box::new)?;
+        // allow ~ for the home directory
+        layer.session_file = layer.session_file.map(|path| expand_home(&path));
+        Ok(layer)
+    }
+
+    pub fn from_env<I>(vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut layer = Layer::default();
+
+        for (key, value) in vars {
+            if let Some(param) = key.strip_prefix(ENV_PARAM_PREFIX) {
+                layer.params.insert(param.to_lowercase(), value);
+                continue;
+            }
+            match key.strip_prefix(ENV_PREFIX) {
+                Some("INPUT_DIR") => layer.input_dir = Some(PathBuf::from(value)),
+                Some("INPUT_FILE") => layer.input_file = Some(value),
+                Some("USER") => layer.user = Some(value),
+                Some("SESSION_FILE") => layer.session_file = Some(expand_home(Path::new(&value))),
+                Some("FORMAT") => layer.format = Some(value.parse()?),
+                Some("PARTS") => layer.parts = Some(parse_parts(&value)?),
+                Some("LOG_FILTER") => layer.log_filter = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(layer)
+    }
+
+    /// Parses `--flag value` and `--flag=value` style arguments
+    pub fn from_args<I>(args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut layer = Layer::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .map(str::to_string)
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ConfigError::Invalid(format!("missing value for {}", flag)))
+            };
+
+            match flag.as_str() {
+                "--verbose" => add_verbosity(&mut layer, 1),
+                _ if flag.len() > 1 && flag[1..].bytes().all(|byte| byte == b'v') => {
+                    add_verbosity(&mut layer, flag.len() - 1)
+                }
+                "--log-filter" => layer.log_filter = Some(value()?),
+                "--input-dir" => layer.input_dir = Some(PathBuf::from(value()?)),
+                "--input" => layer.input_file = Some(value()?),
+                "--user" => layer.user = Some(value()?),
+                "--session-file" => layer.session_file = Some(expand_home(Path::new(&value()?))),
+                "--format" => layer.format = Some(value()?.parse()?),
+                "--part" => {
+                    let parts = parse_parts(&value()?)?;
+                    layer.parts.get_or_insert_with(Vec::new).extend(parts);
+                }
+                "--set" => {
+                    let value = value()?;
+                    let (key, value) = value.split_once('=').ok_or_else(|| {
+                        ConfigError::Invalid(format!(
+                            "expected key=value for --set, got '{}'",
+                            value
+                        ))
+                    })?;
+                    layer.params.insert(key.to_string(), value.to_string());
+                }
+                _ => return Err(ConfigError::Invalid(format!("unknown argument '{}'", arg))),
+            }
+        }
+
+        Ok(layer)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub year: u16,
+    pub day: u8,
+    pub input_dir: PathBuf,
+    pub input_file: String,
+    pub user: Option<String>,
+    pub session_file: PathBuf,
+    pub format: Format,
+    pub parts: Vec<u8>,
+    pub log_filter: Option<String>,
+    pub verbosity: u8,
+    pub params: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Built-in defaults only, without looking at files, environment or arguments
+    pub fn new(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            input_dir: PathBuf::from(DEFAULT_INPUT_DIR),
+            input_file: DEFAULT_INPUT_FILE.to_string(),
+            user: None,
+            session_file: user_config_dir().join("session"),
+            format: Format::Text,
+            parts: DEFAULT_PARTS.to_vec(),
+            log_filter: None,
+            verbosity: 0,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// All layers: repo file, user file, environment and process arguments
+    pub fn load(year: u16, day: u8) -> Result<Self, ConfigError> {
+        Self::load_from(year, day, env::args().skip(1))
+    }
+
+    /// Like [`Config::load`] with `args` instead of the process arguments
+    pub fn load_from<I>(year: u16, day: u8, args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut layers = Vec::new();
+
+        let cwd = env::current_dir().map_err(|err| ConfigError::Io(PathBuf::from("."), err))?;
+        if let Some(path) = find_repo_file(&cwd) {
+            layers.push(Layer::from_file(&path)?);
+        }
+
+        let user_file = user_file();
+        if user_file.is_file() {
+            layers.push(Layer::from_file(&user_file)?);
+        }
+
+        layers.push(Layer::from_env(env::vars())?);
+        layers.push(Layer::from_args(args)?);
+
+        Ok(Self::from_layers(year, day, &layers))
+    }
+
+    pub fn from_layers(year: u16, day: u8, layers: &[Layer]) -> Self {
+        let mut config = Self::new(year, day);
+        let day_key = config.day_key();
+
+        for layer in layers {
+            if let Some(input_dir) = &layer.input_dir {
+                config.input_dir = input_dir.clone();
+            }
+            if let Some(input_file) = &layer.input_file {
+                config.input_file = input_file.clone();
+            }
+            if let Some(user) = &layer.user {
+                config.user = Some(user.clone());
+            }
+            if let Some(session_file) = &layer.session_file {
+                config.session_file = session_file.clone();
+            }
+            if let Some(format) = layer.format {
+                config.format = format;
+            }
+            if let Some(parts) = &layer.parts {
+                config.parts = parts.clone();
+            }
+            if let Some(log_filter) = &layer.log_filter {
+                config.log_filter = Some(log_filter.clone());
+            }
+            if let Some(verbosity) = layer.verbosity {
+                config.verbosity = verbosity;
+            }
+            if let Some(params) = layer.days.get(&day_key) {
+                params.iter().for_each(|(key, value)| {
+                    let value = match value {
+                        toml::Value::String(s) => s.clone(),
+                        value => value.to_string(),
+                    };
+                    config.params.insert(key.clone(), value);
+                });
+            }
+            config.params.extend(layer.params.clone());
+        }
+
+        config
+    }
+
+    /// Key of the per-day table in config files
+    pub fn day_key(&self) -> String {
+        format!("{}/{:02}", self.year, self.day)
+    }
+
+    /// Directory of the day crate below the repository `root`
+    pub fn day_dir(&self, root: &Path) -> PathBuf {
+        root.join(self.year.to_string())
+            .join(format!("{:02}", self.day))
+    }
+
+    /// The input file, or the named input of the configured user
+    pub fn input_path(&self) -> PathBuf {
+        match &self.user {
+            Some(user) => self.input_dir.join(USER_INPUTS_DIR).join(user),
+            None => self.input_dir.join(&self.input_file),
+        }
+    }
+
+    /// Stored answers next to the input, e.g. `data/input.answers.toml`
+    pub fn answers_path(&self) -> PathBuf {
+        let mut path = self.input_path().into_os_string();
+        path.push(ANSWERS_SUFFIX);
+        PathBuf::from(path)
+    }
+
+    /// Same configuration for the named input of `user`
+    pub fn for_user(&self, user: &str) -> Self {
+        Self {
+            user: Some(user.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Names of the stored user inputs of the day in `day_dir`, plain or
+    /// encrypted, sorted
+    pub fn users(&self, day_dir: &Path) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(day_dir.join(&self.input_dir).join(USER_INPUTS_DIR)) else {
+            return Vec::new();
+        };
+        let mut users = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                if !entry.path().is_file()
+                    || name.starts_with('.')
+                    || name.ends_with(ANSWERS_SUFFIX)
+                {
+                    return None;
+                }
+                Some(
+                    name.strip_suffix(crypto::ENCRYPTED_SUFFIX)
+                        .unwrap_or(&name)
+                        .to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        users.sort();
+        users.dedup();
+        users
+    }
+
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.parts.contains(&part)
+    }
+
+    /// Per-day parameter or `default` if it is not configured
+    pub fn param_or<T>(&self, key: &str, default: T) -> Result<T, SolveError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.params.get(key) {
+            Some(value) => value.parse::<T>().map_err(|err| {
+                SolveError::Parse(format!("invalid value '{}' for {}: {}", value, key, err))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    pub fn session_token(&self) -> Result<String, ConfigError> {
+        fs::read_to_string(&self.session_file)
+            .map(|token| token.trim().to_string())
+            .map_err(|err| ConfigError::Io(self.session_file.clone(), err))
+    }
+}
+
+/// Searches `start` and its parents for the repository `aoc.toml`
+pub fn find_repo_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// `$AOC_CONFIG` or `aoc.toml` in the user config directory
+pub fn user_file() -> PathBuf {
+    match env::var_os("AOC_CONFIG") {
+        Some(path) => PathBuf::from(path),
+        None => user_config_dir().join(CONFIG_FILE),
+    }
+}
+
+pub(crate) fn user_config_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME") {
+        PathBuf::from(dir).join("aoc")
+    } else {
+        home_dir().join(".config").join("aoc")
+    }
+}
+
+fn home_dir() -> PathBuf {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home_dir().join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// `-v` flags add up to the highest level
+fn add_verbosity(layer: &mut Layer, count: usize) {
+    let count = count.min(logging::MAX_VERBOSITY as usize) as u8;
+    let verbosity = layer.verbosity.get_or_insert(0);
+    *verbosity = verbosity.saturating_add(count).min(logging::MAX_VERBOSITY);
+}
+
+fn parse_parts(s: &str) -> Result<Vec<u8>, ConfigError> {
+    s.split(',')
+        .map(|part| match part.trim().parse::<u8>() {
+            Ok(part @ 1..=2) => Ok(part),
+            _ => Err(ConfigError::Invalid(format!("invalid part '{}'", part))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_REPO_FILE: &str = r#"
+        input_dir = "./data"
+        parts = [1, 2]
+
+        [days."2021/04"]
+        boards_max = 100
+
+        [days."2021/11"]
+        lines = 10
+        name = "octopus"
+    "#;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::new(2021, 4);
+        assert_eq!(config.input_path(), PathBuf::from("./data/input"));
+        assert_eq!(
+            config.answers_path(),
+            PathBuf::from("./data/input.answers.toml")
+        );
+        assert!(config.runs_part(1));
+        assert!(config.runs_part(2));
+        assert_eq!(config.format, Format::Text);
+        assert_eq!(config.day_key(), "2021/04");
+        assert_eq!(
+            config.day_dir(Path::new("/aoc")),
+            PathBuf::from("/aoc/2021/04")
+        );
+    }
+
+    #[test]
+    fn test_users() {
+        let config =
+            Config::from_layers(2021, 7, &[Layer::from_args(args("--user alice")).unwrap()]);
+        assert_eq!(config.input_path(), PathBuf::from("./data/inputs/alice"));
+        assert_eq!(
+            config.answers_path(),
+            PathBuf::from("./data/inputs/alice.answers.toml")
+        );
+        assert_eq!(
+            Config::new(2021, 7).for_user("bob").input_path(),
+            PathBuf::from("./data/inputs/bob")
+        );
+
+        let day_dir = env::temp_dir().join(format!("aoc-users-{}", std::process::id()));
+        let inputs_dir = day_dir.join("data").join(USER_INPUTS_DIR);
+        fs::create_dir_all(&inputs_dir).unwrap();
+        for file in ["bob", "bob.answers.toml", "alice.enc", ".gitkeep"] {
+            fs::write(inputs_dir.join(file), "").unwrap();
+        }
+        assert_eq!(config.users(&day_dir), vec!["alice", "bob"]);
+        assert!(config.users(&inputs_dir).is_empty());
+        fs::remove_dir_all(&day_dir).unwrap();
+    }
+
+    #[test]
+    fn test_layer_from_toml() {
+        let layer = Layer::from_toml(TEST_REPO_FILE).unwrap();
+        assert_eq!(layer.input_dir, Some(PathBuf::from("./data")));
+        assert_eq!(layer.days.len(), 2);
+
+        assert!(Layer::from_toml("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn test_session_file() {
+        let expanded = Some(home_dir().join(".aoc/session"));
+        let layer = Layer::from_toml("session_file = \"~/.aoc/session\"").unwrap();
+        assert_eq!(layer.session_file, expanded);
+        let vars = [("AOC_SESSION_FILE".to_string(), "~/.aoc/session".to_string())];
+        assert_eq!(Layer::from_env(vars).unwrap().session_file, expanded);
+        let layer = Layer::from_args(args("--session-file ~/.aoc/session")).unwrap();
+        assert_eq!(layer.session_file, expanded);
+    }
+
+    #[test]
+    fn test_day_params() {
+        let layer = Layer::from_toml(TEST_REPO_FILE).unwrap();
+
+        let config = Config::from_layers(2021, 4, std::slice::from_ref(&layer));
+        assert_eq!(config.param_or("boards_max", 0usize), Ok(100));
+        assert_eq!(config.param_or("lines", 5usize), Ok(5));
+
+        let config = Config::from_layers(2021, 11, &[layer]);
+        assert_eq!(config.param_or("lines", 5usize), Ok(10));
+        assert_eq!(
+            config.param_or("name", String::new()),
+            Ok("octopus".to_string())
+        );
+    }
+
+    #[test]
+    fn test_layer_priority() {
+        let file = Layer::from_toml(TEST_REPO_FILE).unwrap();
+        let env = Layer::from_env(vec![
+            ("AOC_INPUT_DIR".to_string(), "/tmp/inputs".to_string()),
+            ("AOC_FORMAT".to_string(), "json".to_string()),
+            ("AOC_PARAM_BOARDS_MAX".to_string(), "50".to_string()),
+            ("HOME".to_string(), "/ignored".to_string()),
+        ])
+        .unwrap();
+        let cli = Layer::from_args(args("--part 2 --set boards_max=10 --input=example")).unwrap();
+
+        let config = Config::from_layers(2021, 4, &[file.clone(), env.clone()]);
+        assert_eq!(config.input_path(), PathBuf::from("/tmp/inputs/input"));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.param_or("boards_max", 0usize), Ok(50));
+        assert_eq!(config.parts, vec![1, 2]);
+
+        let config = Config::from_layers(2021, 4, &[file, env, cli]);
+        assert_eq!(config.input_path(), PathBuf::from("/tmp/inputs/example"));
+        assert_eq!(config.param_or("boards_max", 0usize), Ok(10));
+        assert_eq!(config.parts, vec![2]);
+        assert!(!config.runs_part(1));
+    }
+
+    #[test]
+    fn test_log_args() {
+        let cli = Layer::from_args(args("-v")).unwrap();
+        assert_eq!(cli.verbosity, Some(1));
+        let cli = Layer::from_args(args("-vv -v")).unwrap();
+        assert_eq!(cli.verbosity, Some(3));
+        // capped instead of overflowing
+        let cli = Layer::from_args(args(&format!("-{} -v", "v".repeat(300)))).unwrap();
+        assert_eq!(cli.verbosity, Some(logging::MAX_VERBOSITY));
+        let cli = Layer::from_args(args(&"-v ".repeat(300))).unwrap();
+        assert_eq!(cli.verbosity, Some(logging::MAX_VERBOSITY));
+        let cli = Layer::from_args(args("--verbose")).unwrap();
+        assert_eq!(cli.verbosity, Some(1));
+
+        let env =
+            Layer::from_env(vec![("AOC_LOG_FILTER".to_string(), "debug".to_string())]).unwrap();
+        let cli = Layer::from_args(args("--log-filter aoc_2021_06=trace")).unwrap();
+        let config = Config::from_layers(2021, 6, std::slice::from_ref(&env));
+        assert_eq!(config.log_filter.as_deref(), Some("debug"));
+        let config = Config::from_layers(2021, 6, &[env, cli]);
+        assert_eq!(config.log_filter.as_deref(), Some("aoc_2021_06=trace"));
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert!(Layer::from_args(args("--part 3")).is_err());
+        assert!(Layer::from_args(args("--format xml")).is_err());
+        assert!(Layer::from_args(args("--set boards_max")).is_err());
+        assert!(Layer::from_args(args("--input-dir")).is_err());
+        assert!(Layer::from_args(args("--unknown")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_param() {
+        let cli = Layer::from_args(args("--set boards_max=many")).unwrap();
+        let config = Config::from_layers(2021, 4, &[cli]);
+        assert_eq!(
+            config.param_or("boards_max", 0usize),
+            Err(SolveError::Parse(
+                "invalid value 'many' for boards_max: invalid digit found in string".to_string()
+            ))
+        );
+    }
+}
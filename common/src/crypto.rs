@@ -0,0 +1,282 @@
+//! Encrypted puzzle inputs, so they can be versioned without publishing them.
+//!
+//! The key is derived from a local passphrase with Argon2id and the input is
+//! encrypted with AES-256-GCM. The header with the key derivation parameters,
+//! the salt and the nonce is authenticated too, so a wrong passphrase and a
+//! modified file are both detected.
+
+use std::{
+    collections::HashMap,
+    env, error, fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key as AesKey, Nonce,
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use sha2::{Digest, Sha256};
+
+use crate::config;
+
+pub const ENCRYPTED_SUFFIX: &str = ".enc";
+pub const PASSPHRASE_VAR: &str = "AOC_PASSPHRASE";
+
+const MAGIC: &[u8; 8] = b"AOCENC1\0";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+// magic, three u32 Argon2 parameters, salt and nonce
+const HEADER_LEN: usize = MAGIC.len() + 12 + SALT_LEN + NONCE_LEN;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    NoPassphrase(String),
+    /// Not an encrypted input or truncated
+    Invalid(String),
+    WrongPassphrase,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::NoPassphrase(msg) => write!(f, "no passphrase: {}", msg),
+            CryptoError::Invalid(msg) => write!(f, "invalid encrypted input: {}", msg),
+            CryptoError::WrongPassphrase => {
+                write!(f, "wrong passphrase, or the encrypted input was modified")
+            }
+        }
+    }
+}
+
+impl error::Error for CryptoError {}
+
+/// `$AOC_PASSPHRASE` or the first line of `passphrase` in the user config
+/// directory, next to the session token
+pub fn passphrase() -> Result<String, CryptoError> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+
+    let path = config::user_config_dir().join("passphrase");
+    let contents = fs::read_to_string(&path).map_err(|err| {
+        CryptoError::NoPassphrase(format!(
+            "set {} or write it to {}: {}",
+            PASSPHRASE_VAR,
+            path.display(),
+            err
+        ))
+    })?;
+    match contents.lines().next() {
+        Some(line) if !line.is_empty() => Ok(line.to_string()),
+        _ => Err(CryptoError::NoPassphrase(format!(
+            "{} is empty",
+            path.display()
+        ))),
+    }
+}
+
+/// `data/input.enc` for `data/input`
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut encrypted = path.as_os_str().to_owned();
+    encrypted.push(ENCRYPTED_SUFFIX);
+    PathBuf::from(encrypted)
+}
+
+/// Argon2 cost parameters, stored in the header of every file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Costs {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Derived key, one is used for all inputs encrypted together
+pub struct Key {
+    cipher: Aes256Gcm,
+    costs: Costs,
+    salt: [u8; SALT_LEN],
+}
+
+impl Key {
+    /// Key with a new random salt
+    pub fn new(passphrase: &str, costs: Costs) -> Result<Self, CryptoError> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(passphrase, costs, salt)
+    }
+
+    fn derive(passphrase: &str, costs: Costs, salt: [u8; SALT_LEN]) -> Result<Self, CryptoError> {
+        let params = Params::new(
+            costs.memory_kib,
+            costs.iterations,
+            costs.parallelism,
+            Some(32),
+        )
+        .map_err(|err| CryptoError::Invalid(err.to_string()))?;
+
+        let mut key = [0; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|err| CryptoError::Invalid(err.to_string()))?;
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(AesKey::<Aes256Gcm>::from_slice(&key)),
+            costs,
+            salt,
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut data = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+        data.extend_from_slice(MAGIC);
+        for cost in [
+            self.costs.memory_kib,
+            self.costs.iterations,
+            self.costs.parallelism,
+        ] {
+            data.extend_from_slice(&cost.to_le_bytes());
+        }
+        data.extend_from_slice(&self.salt);
+        data.extend_from_slice(&nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: &data,
+                },
+            )
+            .expect("input too large to encrypt");
+        data.extend(ciphertext);
+        data
+    }
+}
+
+// deriving is slow on purpose, inputs encrypted together share the salt
+type KeyCache = HashMap<([u8; 32], Costs, [u8; SALT_LEN]), Aes256Gcm>;
+
+fn key_cache() -> &'static Mutex<KeyCache> {
+    static CACHE: OnceLock<Mutex<KeyCache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<String, CryptoError> {
+    if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+        return Err(CryptoError::Invalid("unknown format".to_string()));
+    }
+
+    let (header, ciphertext) = data.split_at(HEADER_LEN);
+    let cost = |idx: usize| {
+        let start = MAGIC.len() + idx * 4;
+        u32::from_le_bytes(header[start..start + 4].try_into().unwrap())
+    };
+    let costs = Costs {
+        memory_kib: cost(0),
+        iterations: cost(1),
+        parallelism: cost(2),
+    };
+    let salt_start = MAGIC.len() + 12;
+    let salt: [u8; SALT_LEN] = header[salt_start..salt_start + SALT_LEN]
+        .try_into()
+        .unwrap();
+    let nonce = Nonce::from_slice(&header[salt_start + SALT_LEN..]);
+
+    let cache_key = (Sha256::digest(passphrase).into(), costs, salt);
+    let cached = key_cache().lock().unwrap().get(&cache_key).cloned();
+    let cipher = match cached {
+        Some(cipher) => cipher,
+        None => {
+            let key = Key::derive(passphrase, costs, salt)?;
+            key_cache()
+                .lock()
+                .unwrap()
+                .insert(cache_key, key.cipher.clone());
+            key.cipher
+        }
+    };
+
+    let plaintext = cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| CryptoError::WrongPassphrase)?;
+    String::from_utf8(plaintext).map_err(|err| CryptoError::Invalid(err.to_string()))
+}
+
+/// Decrypts `path` with the local [`passphrase`]
+pub fn decrypt_file(path: &Path) -> Result<String, CryptoError> {
+    let data = fs::read(path).map_err(|err| CryptoError::Invalid(err.to_string()))?;
+    decrypt(&data, &passphrase()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the minimum, tests shouldn't spend time on key derivation
+    const CHEAP: Costs = Costs {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn test_roundtrip() {
+        let key = Key::new("correct horse", CHEAP).unwrap();
+        let data = key.encrypt("3,4,3,1,2\n");
+        assert_eq!(&data[..MAGIC.len()], MAGIC);
+        assert!(!data.windows(5).any(|window| window == b"3,4,3"));
+        assert_eq!(decrypt(&data, "correct horse").unwrap(), "3,4,3,1,2\n");
+
+        // fresh nonce for every input
+        assert_ne!(key.encrypt("3,4,3,1,2\n"), data);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let data = Key::new("correct horse", CHEAP).unwrap().encrypt("input");
+        assert!(matches!(
+            decrypt(&data, "battery staple"),
+            Err(CryptoError::WrongPassphrase)
+        ));
+
+        let mut modified = data.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt(&modified, "correct horse"),
+            Err(CryptoError::WrongPassphrase)
+        ));
+
+        assert!(matches!(
+            decrypt(b"3,4,3,1,2", "correct horse"),
+            Err(CryptoError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_path() {
+        assert_eq!(
+            encrypted_path(Path::new("./data/input")),
+            PathBuf::from("./data/input.enc")
+        );
+    }
+}
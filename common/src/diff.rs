@@ -0,0 +1,292 @@
+//! Differential testing of alternative implementations of a part.
+//!
+//! A day registers extra [`Variant`]s next to its [`Solution::solve`], e.g. a
+//! naive and an optimized version. [`check`] runs all of them on the day's
+//! examples and on generated inputs and stops at the first disagreement.
+
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+    answer::Answer,
+    config::Config,
+    run,
+    solution::{Solution, SolveError},
+};
+
+pub type SolveFn = fn(&str, &Config) -> Result<Answer, SolveError>;
+
+/// Name of the day's own part implementation in reports
+pub const REFERENCE: &str = "solution";
+
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: SolveFn,
+}
+
+impl Variant {
+    pub fn new(part: u8, name: &'static str, solve: SolveFn) -> Self {
+        Self { part, name, solve }
+    }
+}
+
+/// Small deterministic generator so failing inputs can be reproduced by seed
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    // splitmix64
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..high`
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "empty range {}..{}", low, high);
+        low + self.next_u64() % (high - low)
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0, len as u64) as usize
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0, 100) < percent
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Example(usize),
+    Generated(u64),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Example(idx) => write!(f, "example {}", idx + 1),
+            InputSource::Generated(seed) => write!(f, "generated input (seed {})", seed),
+        }
+    }
+}
+
+/// Outcome of one implementation, panics are caught and reported as errors
+pub type Outcome = Result<Answer, String>;
+
+#[derive(Clone, Debug)]
+pub struct Disagreement {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub source: InputSource,
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}/{:02} part {} disagrees on {}:",
+            self.year, self.day, self.part, self.source
+        )?;
+        for (name, outcome) in &self.outcomes {
+            match outcome {
+                Ok(answer) => writeln!(f, "  {}: {}", name, answer)?,
+                Err(err) => writeln!(f, "  {}: error: {}", name, err)?,
+            }
+        }
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub inputs: usize,
+    pub comparisons: usize,
+}
+
+fn outcome(solve: impl FnOnce() -> Result<Answer, SolveError>) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(payload) => Err(format!("panic: {}", run::panic_message(&*payload))),
+    }
+}
+
+// errors of different implementations only have to agree on failing
+fn agree(a: &Outcome, b: &Outcome) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+/// Compares all variants of every part on one input
+pub fn check_input(
+    solution: &dyn Solution,
+    source: InputSource,
+    input: &str,
+    config: &Config,
+) -> Result<usize, Box<Disagreement>> {
+    let variants = solution.variants();
+    let mut parts = variants
+        .iter()
+        .map(|variant| variant.part)
+        .collect::<Vec<_>>();
+    parts.sort_unstable();
+    parts.dedup();
+
+    let mut comparisons = 0;
+    for part in parts {
+        let reference = outcome(|| solution.solve(part, input, config));
+        let mut outcomes = vec![(REFERENCE, reference)];
+
+        for variant in variants.iter().filter(|variant| variant.part == part) {
+            outcomes.push((variant.name, outcome(|| (variant.solve)(input, config))));
+            comparisons += 1;
+
+            let (_, last) = outcomes.last().unwrap();
+            if !agree(&outcomes[0].1, last) {
+                return Err(Box::new(Disagreement {
+                    year: solution.year(),
+                    day: solution.day(),
+                    part,
+                    source,
+                    input: input.to_string(),
+                    outcomes,
+                }));
+            }
+        }
+    }
+
+    Ok(comparisons)
+}
+
+/// Runs the examples and `generated` inputs derived from `seed`
+pub fn check(
+    solution: &dyn Solution,
+    config: &Config,
+    generated: usize,
+    seed: u64,
+) -> Result<Summary, Box<Disagreement>> {
+    let mut summary = Summary::default();
+    if solution.variants().is_empty() {
+        return Ok(summary);
+    }
+
+    let examples = solution.examples();
+    for (idx, example) in examples.iter().enumerate() {
+        summary.comparisons += check_input(solution, InputSource::Example(idx), example, config)?;
+        summary.inputs += 1;
+    }
+
+    for n in 0..generated as u64 {
+        let input_seed = seed.wrapping_add(n);
+        let Some(input) = solution.generate(&mut Rng::new(input_seed)) else {
+            break;
+        };
+        summary.comparisons +=
+            check_input(solution, InputSource::Generated(input_seed), &input, config)?;
+        summary.inputs += 1;
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counting;
+
+    fn count_fast(input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(input.len()))
+    }
+
+    fn count_broken(input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Ok(Answer::from(input.len().min(5)))
+    }
+
+    impl Solution for Counting {
+        fn year(&self) -> u16 {
+            2021
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+            Ok(Answer::from(input.chars().count()))
+        }
+
+        fn part2(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+            Ok(Answer::from(input.len()))
+        }
+
+        fn variants(&self) -> Vec<Variant> {
+            vec![
+                Variant::new(1, "fast", count_fast),
+                Variant::new(2, "broken", count_broken),
+            ]
+        }
+
+        fn examples(&self) -> Vec<&'static str> {
+            vec!["abc"]
+        }
+
+        fn generate(&self, rng: &mut Rng) -> Option<String> {
+            let len = rng.range(0, 10) as usize;
+            Some("a".repeat(len))
+        }
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(a.next_u64(), Rng::new(8).next_u64());
+        (0..100).for_each(|_| assert!((3..6).contains(&a.range(3, 6))));
+    }
+
+    #[test]
+    fn test_first_disagreement() {
+        let config = Config::new(2021, 1);
+        let err = check(&Counting, &config, 100, 0).unwrap_err();
+        assert_eq!(err.part, 2);
+        assert!(matches!(err.source, InputSource::Generated(_)));
+        assert!(err.input.len() > 5);
+        assert_eq!(err.outcomes.len(), 2);
+        assert!(err.to_string().contains("broken: 5"));
+    }
+
+    #[test]
+    fn test_check_input() {
+        let config = Config::new(2021, 1);
+        let comparisons = check_input(&Counting, InputSource::Example(0), "abc", &config);
+        assert_eq!(comparisons.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_panics_are_errors() {
+        let ok = outcome(|| Ok(Answer::from(1u8)));
+        let panicked = outcome(|| panic!("boom"));
+        assert_eq!(panicked, Err("panic: boom".to_string()));
+        assert!(!agree(&ok, &panicked));
+        assert!(agree(&panicked, &Err("other".to_string())));
+    }
+}
@@ -0,0 +1,450 @@
+//! Points, vectors and line segments on the integer grid of the puzzles.
+//!
+//! Coordinates are signed so a walk can leave the map and `y` grows
+//! downwards like the lines of an input. A [`Segment`] includes both ends,
+//! [`Segment::points`] rasterizes it with Bresenham's algorithm and
+//! [`Segment::intersection`] is exact.
+//!
+//! ```
+//! use aoc_common::geometry::{Intersection, Point, Segment};
+//!
+//! let a = Segment::new(Point::new(0, 0), Point::new(4, 4));
+//! let b = Segment::new(Point::new(0, 4), Point::new(4, 0));
+//! assert_eq!(a.intersection(&b), Some(Intersection::Point(Point::new(2, 2))));
+//! assert_eq!(a.points().count(), 5);
+//! assert_eq!(Point::new(0, 4).manhattan(Point::new(4, 0)), 8);
+//! ```
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Difference of two points, e.g. a step or a slope
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Steps along the axes, `|dx| + |dy|`
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// King moves, `max(|dx|, |dy|)`
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The 4 points sharing a side, up, right, down, left
+    pub fn neighbours(self) -> [Point; 4] {
+        [Vector::UP, Vector::RIGHT, Vector::DOWN, Vector::LEFT].map(|step| self + step)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Vector {
+    pub const UP: Vector = Vector::new(0, -1);
+    pub const RIGHT: Vector = Vector::new(1, 0);
+    pub const DOWN: Vector = Vector::new(0, 1);
+    pub const LEFT: Vector = Vector::new(-1, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Each component as -1, 0 or 1, one step of a straight or diagonal line
+    pub fn signum(self) -> Vector {
+        Vector::new(self.x.signum(), self.y.signum())
+    }
+
+    /// z of the cross product, positive if `other` turns counterclockwise
+    /// with `y` up
+    pub fn cross(self, other: Vector) -> i128 {
+        self.x as i128 * other.y as i128 - self.y as i128 * other.x as i128
+    }
+
+    pub fn dot(self, other: Vector) -> i128 {
+        self.x as i128 * other.x as i128 + self.y as i128 * other.y as i128
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+/// Line from `start` to `end`, both included
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Where two segments meet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intersection {
+    Point(Point),
+    /// They cross between the points of the grid
+    OffGrid,
+    /// Collinear segments sharing more than a point
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    pub fn direction(&self) -> Vector {
+        self.end - self.start
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// At 45 degrees, a single point is no diagonal
+    pub fn is_diagonal(&self) -> bool {
+        let v = self.direction();
+        v.x != 0 && v.x.abs() == v.y.abs()
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.start, self.end)
+    }
+
+    /// Grid points from `start` to `end` with Bresenham's algorithm, exact
+    /// for straight and diagonal segments
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (end, v) = (self.end, self.direction());
+        let (dx, dy) = (v.x.abs(), -v.y.abs());
+        let step = v.signum();
+        let mut point = Some(self.start);
+        let mut error = dx + dy;
+        std::iter::from_fn(move || {
+            let current = point?;
+            point = (current != end).then(|| {
+                let mut next = current;
+                let double = 2 * error;
+                if double >= dy {
+                    error += dy;
+                    next.x += step.x;
+                }
+                if double <= dx {
+                    error += dx;
+                    next.y += step.y;
+                }
+                next
+            });
+            Some(current)
+        })
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (point - self.start).cross(self.direction()) == 0 && self.bounds().contains(point)
+    }
+
+    /// The common point or part, `None` if they don't meet
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        let (d1, d2) = (self.direction(), other.direction());
+        let offset = other.start - self.start;
+        let denom = d1.cross(d2);
+
+        if denom == 0 {
+            return self.overlap(other);
+        }
+
+        // start + d1 * t = other.start + d2 * u, with t and u in 0..=1
+        let (t, u) = (offset.cross(d2), offset.cross(d1));
+        let (t, u, denom) = match denom < 0 {
+            true => (-t, -u, -denom),
+            false => (t, u, denom),
+        };
+        if !(0..=denom).contains(&t) || !(0..=denom).contains(&u) {
+            return None;
+        }
+        let (x, y) = (d1.x as i128 * t, d1.y as i128 * t);
+        if x % denom != 0 || y % denom != 0 {
+            return Some(Intersection::OffGrid);
+        }
+        Some(Intersection::Point(Point::new(
+            self.start.x + (x / denom) as i64,
+            self.start.y + (y / denom) as i64,
+        )))
+    }
+
+    /// Intersection of parallel segments
+    fn overlap(&self, other: &Segment) -> Option<Intersection> {
+        let d = match (self.direction(), other.direction()) {
+            (Vector { x: 0, y: 0 }, Vector { x: 0, y: 0 }) => {
+                return (self.start == other.start).then_some(Intersection::Point(self.start));
+            }
+            (Vector { x: 0, y: 0 }, d) => d,
+            (d, _) => d,
+        };
+        if (other.start - self.start).cross(d) != 0 || (other.end - self.start).cross(d) != 0 {
+            return None;
+        }
+
+        // order the ends along the common line
+        let key = |p: Point| (p - Point::ORIGIN).dot(d);
+        let sorted = |a: Point, b: Point| if key(a) <= key(b) { (a, b) } else { (b, a) };
+        let (a, b) = sorted(self.start, self.end);
+        let (c, e) = sorted(other.start, other.end);
+        let low = if key(a) >= key(c) { a } else { c };
+        let high = if key(b) <= key(e) { b } else { e };
+        match key(low).cmp(&key(high)) {
+            std::cmp::Ordering::Greater => None,
+            std::cmp::Ordering::Equal => Some(Intersection::Point(low)),
+            std::cmp::Ordering::Less => Some(Intersection::Overlap(Segment::new(low, high))),
+        }
+    }
+}
+
+/// Axis-aligned box, both corners included
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The box spanned by two opposite corners
+    pub fn new(a: Point, b: Point) -> Self {
+        Self {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Smallest box around all `points`, `None` for none
+    pub fn bounding(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        points.into_iter().fold(None, |rect, p| {
+            Some(rect.map_or(Rect::new(p, p), |rect: Rect| rect.union(&Rect::new(p, p))))
+        })
+    }
+
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::new(
+            Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Columns, including both sides
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    /// Rows, including both sides
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(x1: i64, y1: i64, x2: i64, y2: i64) -> Segment {
+        Segment::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    #[test]
+    fn test_points_and_vectors() {
+        let p = Point::new(3, -2);
+        assert_eq!(p + Vector::new(1, 1), Point::new(4, -1));
+        assert_eq!(p - Point::new(1, 1), Vector::new(2, -3));
+        assert_eq!(Vector::new(-7, 0).signum(), Vector::LEFT);
+        assert_eq!(-Vector::UP * 3, Vector::new(0, 3));
+        assert_eq!(p.manhattan(Point::ORIGIN), 5);
+        assert_eq!(p.chebyshev(Point::ORIGIN), 3);
+        assert_eq!(p.neighbours()[0], Point::new(3, -3));
+        assert_eq!(p.to_string(), "3,-2");
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let points = |s: Segment| s.points().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(points(segment(2, 2, 2, 1)), [(2, 2), (2, 1)]);
+        assert_eq!(points(segment(9, 4, 7, 4)), [(9, 4), (8, 4), (7, 4)]);
+        assert_eq!(
+            points(segment(5, 5, 8, 2)),
+            [(5, 5), (6, 4), (7, 3), (8, 2)]
+        );
+        assert_eq!(points(segment(1, 1, 1, 1)), [(1, 1)]);
+        assert_eq!(
+            points(segment(0, 0, 4, 2)),
+            [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+
+        let steep = segment(0, 0, -2, 7);
+        assert!(steep.points().all(|p| steep.bounds().contains(p)));
+        assert_eq!(steep.points().count(), 8);
+        assert_eq!(steep.points().last(), Some(steep.end));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let diagonal = segment(0, 0, 8, 8);
+        assert_eq!(
+            diagonal.intersection(&segment(8, 0, 0, 8)),
+            Some(Intersection::Point(Point::new(4, 4)))
+        );
+        assert_eq!(
+            segment(0, 0, 1, 1).intersection(&segment(1, 0, 0, 1)),
+            Some(Intersection::OffGrid)
+        );
+        assert_eq!(diagonal.intersection(&segment(1, 0, 9, 8)), None);
+        assert_eq!(diagonal.intersection(&segment(9, 0, 9, 9)), None);
+        // touching ends
+        assert_eq!(
+            diagonal.intersection(&segment(8, 8, 8, 12)),
+            Some(Intersection::Point(Point::new(8, 8)))
+        );
+        assert_eq!(
+            segment(0, 9, 5, 9).intersection(&segment(2, 9, 0, 9)),
+            Some(Intersection::Overlap(segment(0, 9, 2, 9)))
+        );
+        assert_eq!(
+            segment(0, 9, 5, 9).intersection(&segment(5, 9, 7, 9)),
+            Some(Intersection::Point(Point::new(5, 9)))
+        );
+        assert_eq!(segment(0, 9, 5, 9).intersection(&segment(6, 9, 7, 9)), None);
+        assert_eq!(
+            segment(3, 3, 3, 3).intersection(&diagonal),
+            Some(Intersection::Point(Point::new(3, 3)))
+        );
+    }
+
+    #[test]
+    fn test_intersection_matches_rasterization() {
+        // every pair of short straight and diagonal segments in a 4x4 box
+        let mut segments = Vec::new();
+        for (x, y) in (0..4).flat_map(|x| (0..4).map(move |y| (x, y))) {
+            for step in [
+                Vector::RIGHT,
+                Vector::DOWN,
+                Vector::new(1, 1),
+                Vector::new(1, -1),
+            ] {
+                for len in 0..3 {
+                    let start = Point::new(x, y);
+                    segments.push(Segment::new(start, start + step * len));
+                }
+            }
+        }
+        for a in &segments {
+            for b in &segments {
+                let common = a.points().filter(|p| b.contains(*p)).collect::<Vec<_>>();
+                let expected = match common.len() {
+                    0 => None,
+                    1 => Some(Intersection::Point(common[0])),
+                    _ => Some(Intersection::Overlap(Segment::new(
+                        common[0],
+                        common[common.len() - 1],
+                    ))),
+                };
+                let normalize = |i: Option<Intersection>| match i {
+                    Some(Intersection::Overlap(s)) => Some(Rect::new(s.start, s.end)),
+                    Some(Intersection::Point(p)) => Some(Rect::new(p, p)),
+                    _ => None,
+                };
+                assert_eq!(
+                    normalize(a.intersection(b)),
+                    normalize(expected),
+                    "{:?} {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::bounding([Point::new(3, 1), Point::new(-1, 4), Point::new(0, 0)]).unwrap();
+        assert_eq!(rect, Rect::new(Point::new(-1, 0), Point::new(3, 4)));
+        assert_eq!((rect.width(), rect.height()), (5, 5));
+        assert!(rect.contains(Point::new(3, 4)));
+        assert!(!rect.contains(Point::new(4, 4)));
+        assert_eq!(Rect::bounding([]), None);
+    }
+}
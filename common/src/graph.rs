@@ -0,0 +1,509 @@
+//! Graphs of puzzle inputs: caves, height maps, mazes.
+//!
+//! Nodes are interned, a key like `"start"` or `(x, y)` maps to a dense
+//! [`NodeId`] once and the searches work on ids and adjacency lists. Every
+//! node carries an attribute of the day's own type, e.g. a small or a large
+//! cave. Edges are directed and weighted, [`Graph::add_edges`] adds both
+//! directions.
+//!
+//! ```
+//! use aoc_common::graph::Graph;
+//!
+//! let mut graph = Graph::<&str>::new();
+//! graph.add_edges("start", "a", 1);
+//! graph.add_edges("a", "end", 1);
+//! graph.add_edges("start", "end", 5);
+//!
+//! let start = graph.id(&"start").unwrap();
+//! let end = graph.id(&"end").unwrap();
+//! let (cost, path) = graph.shortest_path(start, end, |_| 0).unwrap();
+//! assert_eq!(cost, 2);
+//! assert_eq!(path.iter().map(|&id| *graph.key(id)).collect::<Vec<_>>(), ["start", "a", "end"]);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Dense index of a node, in the order the nodes were added
+pub type NodeId = usize;
+
+// `count_paths` keeps the visited limited nodes in a bit set
+const MAX_LIMITED: usize = u128::BITS as usize;
+
+#[derive(Clone, Debug)]
+pub struct Graph<K, A = ()> {
+    ids: HashMap<K, NodeId>,
+    keys: Vec<K>,
+    attrs: Vec<A>,
+    edges: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl<K, A> Default for Graph<K, A> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            keys: Vec::new(),
+            attrs: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, A> Graph<K, A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the node `key`, a new node gets `attr`, an existing one keeps
+    /// its attribute
+    pub fn add_node_with(&mut self, key: K, attr: A) -> NodeId {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.keys.len();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        self.attrs.push(attr);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, key: &K) -> Option<NodeId> {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: NodeId) -> &K {
+        &self.keys[id]
+    }
+
+    pub fn attr(&self, id: NodeId) -> &A {
+        &self.attrs[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// All ids, in the order the nodes were added
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len()
+    }
+
+    /// Directed edge between two existing nodes
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    /// Targets and weights of the edges leaving `id`
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.edges[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl DoubleEndedIterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// Nodes reachable from `start` in breadth-first order, `start` first
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for next in self.neighbours(id) {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder, neighbours in
+    /// the order their edges were added
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+            stack.extend(self.neighbours(id).filter(|&next| !seen[next]).rev());
+        }
+        order
+    }
+
+    /// Cost of the cheapest path from `start` to every node, `None` for the
+    /// unreachable ones
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<u64>> {
+        let mut costs = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((cost, id))) = queue.pop() {
+            if costs[id].is_some() {
+                continue;
+            }
+            costs[id] = Some(cost);
+            for &(next, weight) in self.edges(id) {
+                if costs[next].is_none() {
+                    queue.push(Reverse((cost + weight, next)));
+                }
+            }
+        }
+        costs
+    }
+
+    /// Cost and nodes of the cheapest path with A*, `heuristic` must never
+    /// overestimate the cost to `goal`. A heuristic of 0 is Dijkstra.
+    pub fn shortest_path(
+        &self,
+        start: NodeId,
+        goal: NodeId,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut costs = vec![u64::MAX; self.len()];
+        let mut previous = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((heuristic(start), start))]);
+        costs[start] = 0;
+        while let Some(Reverse((_, id))) = queue.pop() {
+            if id == goal {
+                let mut path = vec![goal];
+                while let Some(prev) = previous[path[path.len() - 1]] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((costs[goal], path));
+            }
+            for &(next, weight) in self.edges(id) {
+                let cost = costs[id] + weight;
+                if cost < costs[next] {
+                    costs[next] = cost;
+                    previous[next] = Some(id);
+                    queue.push(Reverse((cost + heuristic(next), next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Groups of nodes connected in either direction, each sorted and the
+    /// groups by their first node
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        fn root(parents: &mut [NodeId], mut id: NodeId) -> NodeId {
+            while parents[id] != id {
+                parents[id] = parents[parents[id]];
+                id = parents[id];
+            }
+            id
+        }
+
+        let mut parents = self.ids().collect::<Vec<_>>();
+        for id in self.ids() {
+            for next in self.neighbours(id) {
+                let (a, b) = (root(&mut parents, id), root(&mut parents, next));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components = Vec::<Vec<NodeId>>::new();
+        let mut index = HashMap::new();
+        for id in self.ids() {
+            let root = root(&mut parents, id);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[i].push(id);
+        }
+        components
+    }
+
+    /// Number of paths from `start` to `end` that enter every node where
+    /// `is_limited` holds at most once, except for up to `revisits` extra
+    /// visits in total. `start` is never entered again and a path ends at
+    /// `end`.
+    ///
+    /// Paths are counted per node, visited limited nodes and revisits left,
+    /// so two adjacent unlimited nodes would loop forever. Panics with more
+    /// than 128 limited nodes.
+    pub fn count_paths(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        revisits: usize,
+        is_limited: impl Fn(NodeId) -> bool,
+    ) -> u64 {
+        let walk = PathWalk::new(self, start, end, is_limited);
+        let mut memo = HashMap::new();
+        walk.count(start, walk.enter(0, start), revisits, &mut memo)
+    }
+
+    /// All paths of [`Graph::count_paths`], in the order of the edges
+    pub fn paths(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        revisits: usize,
+        is_limited: impl Fn(NodeId) -> bool,
+    ) -> Vec<Vec<NodeId>> {
+        let walk = PathWalk::new(self, start, end, is_limited);
+        let mut paths = Vec::new();
+        walk.collect(&mut vec![start], walk.enter(0, start), revisits, &mut paths);
+        paths
+    }
+}
+
+impl<K: Clone + Eq + Hash, A: Default> Graph<K, A> {
+    /// Id of the node `key`, added with the default attribute
+    pub fn add_node(&mut self, key: K) -> NodeId {
+        self.add_node_with(key, A::default())
+    }
+
+    /// Edges in both directions, adding missing nodes
+    pub fn add_edges(&mut self, a: K, b: K, weight: u64) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+}
+
+/// Depth-first walk of the paths of `count_paths` and `paths`
+struct PathWalk<'g, K, A> {
+    graph: &'g Graph<K, A>,
+    start: NodeId,
+    end: NodeId,
+    // bit of every limited node
+    bits: Vec<Option<u32>>,
+}
+
+type PathMemo = HashMap<(NodeId, u128, usize), u64>;
+
+impl<'g, K: Clone + Eq + Hash, A> PathWalk<'g, K, A> {
+    fn new(
+        graph: &'g Graph<K, A>,
+        start: NodeId,
+        end: NodeId,
+        is_limited: impl Fn(NodeId) -> bool,
+    ) -> Self {
+        let mut limited = 0;
+        let bits = graph
+            .ids()
+            .map(|id| {
+                is_limited(id).then(|| {
+                    limited += 1;
+                    limited - 1
+                })
+            })
+            .collect();
+        assert!(
+            limited as usize <= MAX_LIMITED,
+            "{} limited nodes, at most {} are supported",
+            limited,
+            MAX_LIMITED
+        );
+        Self {
+            graph,
+            start,
+            end,
+            bits,
+        }
+    }
+
+    fn enter(&self, visited: u128, id: NodeId) -> u128 {
+        self.bits[id].map_or(visited, |bit| visited | 1 << bit)
+    }
+
+    /// Visited limited nodes and revisits left after entering `next`, `None`
+    /// if it can't be entered
+    fn step(&self, visited: u128, revisits: usize, next: NodeId) -> Option<(u128, usize)> {
+        match self.bits[next] {
+            _ if next == self.start => None,
+            Some(bit) if visited & 1 << bit != 0 => {
+                revisits.checked_sub(1).map(|left| (visited, left))
+            }
+            _ => Some((self.enter(visited, next), revisits)),
+        }
+    }
+
+    fn count(&self, id: NodeId, visited: u128, revisits: usize, memo: &mut PathMemo) -> u64 {
+        if id == self.end {
+            return 1;
+        }
+        if let Some(&count) = memo.get(&(id, visited, revisits)) {
+            return count;
+        }
+        let count = self
+            .graph
+            .neighbours(id)
+            .filter_map(|next| {
+                let (visited, revisits) = self.step(visited, revisits, next)?;
+                Some(self.count(next, visited, revisits, memo))
+            })
+            .sum();
+        memo.insert((id, visited, revisits), count);
+        count
+    }
+
+    fn collect(
+        &self,
+        path: &mut Vec<NodeId>,
+        visited: u128,
+        revisits: usize,
+        paths: &mut Vec<Vec<NodeId>>,
+    ) {
+        let id = path[path.len() - 1];
+        if id == self.end {
+            paths.push(path.clone());
+            return;
+        }
+        for next in self.graph.neighbours(id) {
+            if let Some((visited, revisits)) = self.step(visited, revisits, next) {
+                path.push(next);
+                self.collect(path, visited, revisits, paths);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    enum Size {
+        #[default]
+        Small,
+        Large,
+    }
+
+    /// The first cave example of 2021/12
+    fn caves() -> Graph<&'static str, Size> {
+        let mut graph = Graph::new();
+        graph.add_node_with("A", Size::Large);
+        for (a, b) in [
+            ("start", "A"),
+            ("start", "b"),
+            ("A", "c"),
+            ("A", "b"),
+            ("b", "d"),
+            ("A", "end"),
+            ("b", "end"),
+        ] {
+            graph.add_edges(a, b, 1);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut graph = Graph::<(usize, usize), u8>::new();
+        let a = graph.add_node_with((0, 0), 5);
+        let b = graph.add_node_with((1, 0), 7);
+        assert_eq!(graph.add_node_with((0, 0), 9), a);
+        assert_eq!((graph.len(), *graph.attr(a), *graph.attr(b)), (2, 5, 7));
+        assert_eq!(graph.key(b), &(1, 0));
+        assert_eq!(graph.id(&(2, 0)), None);
+
+        graph.add_edge(a, b, 3);
+        assert_eq!(graph.edges(a), &[(b, 3)]);
+        assert_eq!(graph.neighbours(b).count(), 0);
+    }
+
+    #[test]
+    fn test_traversal() {
+        let graph = caves();
+        let start = graph.id(&"start").unwrap();
+        let names = |ids: Vec<NodeId>| ids.iter().map(|&id| *graph.key(id)).collect::<Vec<_>>();
+        assert_eq!(
+            names(graph.bfs(start)),
+            ["start", "A", "b", "c", "end", "d"]
+        );
+        assert_eq!(
+            names(graph.dfs(start)),
+            ["start", "A", "c", "b", "d", "end"]
+        );
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        // a 3x3 grid, the middle is expensive to enter
+        let mut graph = Graph::<(u64, u64)>::new();
+        for y in 0..3 {
+            for x in 0..3 {
+                let cost = |(x, y)| if (x, y) == (1, 1) { 10 } else { 1 };
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    if nx < 3 && ny < 3 {
+                        let (a, b) = (graph.add_node((x, y)), graph.add_node((nx, ny)));
+                        graph.add_edge(a, b, cost((nx, ny)));
+                        graph.add_edge(b, a, cost((x, y)));
+                    }
+                }
+            }
+        }
+        let start = graph.id(&(0, 0)).unwrap();
+        let goal = graph.id(&(2, 2)).unwrap();
+
+        let costs = graph.dijkstra(start);
+        assert_eq!(costs[goal], Some(4));
+        assert_eq!(costs[graph.id(&(1, 1)).unwrap()], Some(11));
+
+        let manhattan = |id| {
+            let (x, y) = *graph.key(id);
+            (2 - x) + (2 - y)
+        };
+        let (cost, path) = graph.shortest_path(start, goal, manhattan).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.len(), 5);
+        assert!(!path.contains(&graph.id(&(1, 1)).unwrap()));
+
+        let lonely = graph.add_node((9, 9));
+        assert_eq!(graph.shortest_path(start, lonely, |_| 0), None);
+        assert_eq!(graph.dijkstra(start)[lonely], None);
+    }
+
+    #[test]
+    fn test_components() {
+        let mut graph = Graph::<u8>::new();
+        graph.add_edges(1, 2, 1);
+        graph.add_edges(3, 4, 1);
+        graph.add_node(5);
+        let a = graph.add_node(6);
+        let b = graph.id(&2).unwrap();
+        // one direction is enough
+        graph.add_edge(a, b, 1);
+        assert_eq!(graph.components(), vec![vec![0, 1, 5], vec![2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = caves();
+        let (start, end) = (graph.id(&"start").unwrap(), graph.id(&"end").unwrap());
+        let is_small = |id| *graph.attr(id) == Size::Small;
+
+        assert_eq!(graph.count_paths(start, end, 0, is_small), 10);
+        assert_eq!(graph.count_paths(start, end, 1, is_small), 36);
+
+        let paths = graph.paths(start, end, 0, is_small);
+        assert_eq!(paths.len(), 10);
+        assert_eq!(
+            paths[0]
+                .iter()
+                .map(|&id| *graph.key(id))
+                .collect::<Vec<_>>(),
+            ["start", "A", "c", "A", "b", "A", "end"]
+        );
+        assert_eq!(graph.paths(start, end, 1, is_small).len(), 36);
+    }
+}
@@ -0,0 +1,52 @@
+//! Reading puzzle inputs.
+
+use std::{fs, io, path::Path};
+
+use crate::crypto;
+
+/// Falls back to the encrypted input, e.g. `data/input.enc`, if the plain
+/// input is missing
+pub fn read_input(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let encrypted = crypto::encrypted_path(path);
+            if !encrypted.is_file() {
+                return Err(err);
+            }
+            crypto::decrypt_file(&encrypted).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", encrypted.display(), err),
+                )
+            })
+        }
+        result => result,
+    }
+}
+
+/// Like [`read_input`] but panics, for tests on the stored input
+pub fn get_input(path: &Path) -> String {
+    read_input(path).unwrap_or_else(|err| panic!("file not found: {}: {}", path.display(), err))
+}
+
+/// The stored input for tests, `None` with a message if a fresh clone has
+/// neither the plain input nor a passphrase to decrypt it. A broken input
+/// still panics.
+pub fn stored_input(path: &Path) -> Option<String> {
+    if !path.is_file() {
+        let encrypted = crypto::encrypted_path(path);
+        if !encrypted.is_file() {
+            eprintln!("skipped, no input at {}", path.display());
+            return None;
+        }
+        if let Err(err) = crypto::passphrase() {
+            eprintln!(
+                "skipped, {} can't be decrypted: {}",
+                encrypted.display(),
+                err
+            );
+            return None;
+        }
+    }
+    Some(get_input(path))
+}
@@ -0,0 +1,27 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+pub mod answer;
+pub mod answers;
+pub mod bits;
+pub mod config;
+pub mod crypto;
+pub mod diff;
+pub mod geometry;
+pub mod graph;
+pub mod input;
+pub mod logging;
+pub mod math;
+pub mod parse;
+pub mod plugin;
+pub mod repl;
+pub mod run;
+pub mod schedule;
+pub mod simulation;
+pub mod snapshot;
+pub mod solution;
+
+pub use answer::{Answer, Grid};
+pub use config::Config;
+pub use solution::{Abort, Solution, SolveError};
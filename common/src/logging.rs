@@ -0,0 +1,70 @@
+//! Tracing setup for the day binaries.
+//!
+//! Events are written to stderr so stdout only carries the answers.
+
+use std::io::{self, IsTerminal};
+
+use tracing::Span;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::{Config, ConfigError};
+
+// indexed by verbosity, warnings are always shown
+const LEVELS: &[&str] = &["warn", "info", "debug", "trace"];
+/// More `-v` flags don't log more
+pub const MAX_VERBOSITY: u8 = LEVELS.len() as u8 - 1;
+
+/// Filter directives from `--log-filter` or the `-v` count
+pub fn filter(config: &Config) -> String {
+    match &config.log_filter {
+        Some(filter) => filter.clone(),
+        None => LEVELS[config.verbosity.min(MAX_VERBOSITY) as usize].to_string(),
+    }
+}
+
+pub fn init(config: &Config) -> Result<(), ConfigError> {
+    let filter = filter(config);
+    let env_filter = EnvFilter::try_new(&filter)
+        .map_err(|err| ConfigError::Invalid(format!("invalid log filter '{}': {}", filter, err)))?;
+
+    // ignore a subscriber installed before, e.g. by a test
+    let _ = tracing_subscriber::fmt()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_env_filter(env_filter)
+        .without_time()
+        .try_init();
+
+    Ok(())
+}
+
+pub fn day_span(config: &Config) -> Span {
+    tracing::info_span!("day", year = config.year, day = config.day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let mut config = Config::new(2021, 6);
+        assert_eq!(filter(&config), "warn");
+
+        config.verbosity = 2;
+        assert_eq!(filter(&config), "debug");
+
+        config.verbosity = 9;
+        assert_eq!(filter(&config), "trace");
+
+        config.log_filter = Some("aoc_2021_06=trace".to_string());
+        assert_eq!(filter(&config), "aoc_2021_06=trace");
+    }
+
+    #[test]
+    fn test_init_invalid_filter() {
+        let mut config = Config::new(2021, 6);
+        config.log_filter = Some("[".to_string());
+        assert!(init(&config).is_err());
+    }
+}
@@ -0,0 +1,453 @@
+//! Number theory and linear algebra of the puzzles: gcd and lcm, the Chinese
+//! remainder theorem, modular arithmetic and powers of small matrices.
+//!
+//! The integer functions are generic over [`Int`], the primitive integers
+//! with checked arithmetic, and return `None` on overflow instead of a wrong
+//! answer. [`Matrix`] works over any [`Ring`]: the primitive integers,
+//! [`Mod`] for answers modulo a prime and [`BigInt`] when nothing fits.
+//!
+//! ```
+//! use aoc_common::math::{self, Matrix};
+//!
+//! assert_eq!(math::lcm(4u64, 6), Some(12));
+//! // x = 2 mod 3, x = 3 mod 5, x = 2 mod 7
+//! assert_eq!(math::crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+//!
+//! // Fibonacci numbers in log time
+//! let fib = Matrix::new([[1u64, 1], [1, 0]]).pow(90);
+//! assert_eq!(fib.apply(&[1, 0])[1], 2_880_067_194_370_816_120);
+//! ```
+
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+pub use num_bigint::BigInt;
+
+/// Primitive integer with checked arithmetic
+pub trait Int:
+    Copy
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    /// `None` for the minimum of a signed type
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($abs:ident: $($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    <$t>::checked_div(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, other)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    impl_int!(@$abs self)
+                }
+            }
+        )*
+    };
+    (@signed $n:ident) => { $n.checked_abs() };
+    (@unsigned $n:ident) => { Some($n) };
+}
+
+impl_int!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_int!(signed: i8, i16, i32, i64, i128, isize);
+
+/// Greatest common divisor, never negative, `gcd(0, 0)` is 0
+pub fn gcd<T: Int>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    Some(a)
+}
+
+/// Least common multiple, never negative, 0 if either is 0
+pub fn lcm<T: Int>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let (a, b) = (a.checked_abs()?, b.checked_abs()?);
+    (a / gcd(a, b)?).checked_mul(b)
+}
+
+/// Least common multiple of all `numbers`, 1 for none
+pub fn lcm_all<T: Int>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers.into_iter().try_fold(T::ONE, lcm)
+}
+
+/// `n * (n + 1) / 2`, the sum of `1..=n`
+pub fn triangular<T: Int>(n: T) -> Option<T> {
+    let two = T::ONE + T::ONE;
+    // halve the even factor first, the product may still fit
+    match n % two == T::ZERO {
+        true => (n / two).checked_mul(n.checked_add(T::ONE)?),
+        false => n.checked_mul(n.checked_add(T::ONE)? / two),
+    }
+}
+
+/// `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    match r0 < 0 {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    }
+}
+
+/// Inverse of `a` modulo `modulus`, `None` if they share a factor
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+    (g == 1).then(|| x.rem_euclid(modulus as i128) as u64)
+}
+
+/// `base^exp mod modulus` by squaring
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus 0");
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Smallest `x >= 0` with `x = residue mod modulus` for every congruence and
+/// the lcm of the moduli, the period of all solutions. The moduli don't have
+/// to be coprime, `None` if the congruences contradict each other or the
+/// lcm overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, period): (i128, i128), &(residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+            // x + period * k = residue (mod modulus)
+            let (g, inverse, _) = extended_gcd(period, modulus);
+            let diff = residue.checked_sub(x)?;
+            if diff % g != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let k = (diff / g % step)
+                .checked_mul(inverse % step)?
+                .rem_euclid(step);
+            let lcm = period.checked_mul(step)?;
+            let x = x.checked_add(period.checked_mul(k)?)?.rem_euclid(lcm);
+            Some((x, lcm))
+        })
+}
+
+/// Numbers with addition and multiplication, the elements of a [`Matrix`]
+pub trait Ring: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+}
+
+macro_rules! impl_ring {
+    ($($t:ty),*) => {
+        $(
+            /// Panics on overflow in debug builds, like the operators
+            impl Ring for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn add(&self, other: &Self) -> Self {
+                    self + other
+                }
+
+                fn mul(&self, other: &Self) -> Self {
+                    self * other
+                }
+            }
+        )*
+    };
+}
+
+impl_ring!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Ring for BigInt {
+    fn zero() -> Self {
+        BigInt::from(0)
+    }
+
+    fn one() -> Self {
+        BigInt::from(1)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+}
+
+/// Integer modulo `M`, e.g. `Mod<1_000_000_007>`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Mod<const M: u64>(u64);
+
+impl<const M: u64> Mod<M> {
+    pub fn new(n: u64) -> Self {
+        Self(n % M)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        Self(mod_pow(self.0, exp, M))
+    }
+
+    /// `None` if `M` and the value share a factor
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.0, M).map(Self)
+    }
+}
+
+impl<const M: u64> fmt::Display for Mod<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Ring for Mod<M> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self(((self.0 as u128 + other.0 as u128) % M as u128) as u64)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self((self.0 as u128 * other.0 as u128 % M as u128) as u64)
+    }
+}
+
+/// Square `N`x`N` matrix, rows first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<T, const N: usize>(pub [[T; N]; N]);
+
+impl<T: Ring, const N: usize> Matrix<T, N> {
+    pub fn new(rows: [[T; N]; N]) -> Self {
+        Self(rows)
+    }
+
+    pub fn identity() -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| if row == col { T::one() } else { T::zero() })
+        }))
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| {
+                (0..N).fold(T::zero(), |sum, k| {
+                    sum.add(&self.0[row][k].mul(&other.0[k][col]))
+                })
+            })
+        }))
+    }
+
+    /// `self^exp` with `O(log exp)` multiplications
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::identity();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// The matrix times the column `vector`
+    pub fn apply(&self, vector: &[T; N]) -> [T; N] {
+        std::array::from_fn(|row| {
+            (0..N).fold(T::zero(), |sum, k| sum.add(&self.0[row][k].mul(&vector[k])))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const P: u64 = 1_000_000_007;
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(gcd(0u8, 0), Some(0));
+        assert_eq!(gcd(-12i32, 18), Some(6));
+        assert_eq!(gcd(i8::MIN, 2), None);
+        assert_eq!(lcm(0u8, 7), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+        assert_eq!(lcm_all([2u32, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u32>::new()), Some(1));
+        assert_eq!(triangular(0u16), Some(0));
+        assert_eq!(triangular(65535u32), Some(2_147_450_880));
+        assert_eq!(triangular(u32::MAX), None);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(0, 0)]), None);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let m = Matrix::new([[Mod::<P>::new(2), Mod::new(1)], [Mod::new(0), Mod::new(3)]]);
+        assert_eq!(m.pow(0), Matrix::identity());
+        assert_eq!(m.pow(3), m.mul(&m).mul(&m));
+
+        // beyond u128
+        let fib = Matrix::new([
+            [BigInt::from(1), BigInt::from(1)],
+            [BigInt::from(1), BigInt::from(0)],
+        ]);
+        assert_eq!(
+            fib.pow(200).0[0][1].to_string(),
+            "280571172992510140037611932413038677189525"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_gcd_lcm(a in 1u64..1 << 32, b in 1u64..1 << 32) {
+            let g = gcd(a, b).unwrap();
+            prop_assert_eq!((a % g, b % g), (0, 0));
+            prop_assert_eq!(gcd(a / g, b / g), Some(1));
+            prop_assert_eq!(g * lcm(a, b).unwrap(), a * b);
+        }
+
+        #[test]
+        fn prop_extended_gcd(a in -1_000_000i128..1_000_000, b in -1_000_000i128..1_000_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(a * x + b * y, g);
+            prop_assert_eq!(g, gcd(a, b).unwrap());
+        }
+
+        #[test]
+        fn prop_triangular(n in 0u64..2000) {
+            prop_assert_eq!(triangular(n), Some((1..=n).sum()));
+        }
+
+        #[test]
+        fn prop_mod_pow(base in any::<u64>(), exp in 0u64..64, modulus in 1..=u64::MAX) {
+            let expected = (0..exp).fold(1 % modulus as u128, |acc, _| {
+                acc * (base % modulus) as u128 % modulus as u128
+            });
+            prop_assert_eq!(mod_pow(base, exp, modulus) as u128, expected);
+        }
+
+        #[test]
+        fn prop_mod_inverse(a in 1u64..P) {
+            let inverse = Mod::<P>::new(a).inverse().unwrap();
+            prop_assert_eq!(Mod::new(a).mul(&inverse), Mod::one());
+            // Fermat
+            prop_assert_eq!(inverse, Mod::new(a).pow(P - 2));
+        }
+
+        #[test]
+        fn prop_crt(
+            congruences in prop::collection::vec((0i128..1000, 1i128..20), 0..5)
+        ) {
+            let congruences = congruences
+                .iter()
+                .map(|&(residue, modulus)| (residue % modulus, modulus))
+                .collect::<Vec<_>>();
+            let brute = |period: i128| {
+                (0..period).find(|x| congruences.iter().all(|&(r, m)| x % m == r))
+            };
+            let period = congruences
+                .iter()
+                .fold(1, |period, &(_, modulus)| lcm(period, modulus).unwrap());
+            match crt(&congruences) {
+                Some((x, lcm)) => {
+                    prop_assert_eq!(lcm, period);
+                    prop_assert_eq!(brute(period), Some(x));
+                }
+                None => prop_assert_eq!(brute(period), None),
+            }
+        }
+
+        #[test]
+        fn prop_matrix_pow(cells in prop::array::uniform3(prop::array::uniform3(any::<u64>())), a in 0u64..50, b in 0u64..50) {
+            let m = Matrix::new(cells.map(|row| row.map(Mod::<P>::new)));
+            prop_assert_eq!(m.pow(a + b), m.pow(a).mul(&m.pow(b)));
+            let v = [Mod::new(1), Mod::new(2), Mod::new(3)];
+            prop_assert_eq!(m.pow(a).apply(&m.pow(b).apply(&v)), m.pow(a + b).apply(&v));
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Parsing the common shapes of puzzle inputs, with the line and column of
+//! every error.
+//!
+//! A [`Parser`] is a cursor over a slice of the input. Splitting it into
+//! lines, blank-line-separated records, comma-separated items or whitespace
+//! separated words yields parsers of the parts, so an error always points
+//! into the whole input. Words and values are slices of the input, nothing
+//! is copied.
+//!
+//! Whitespace between tokens is insignificant: [`Parser::number`],
+//! [`Parser::word`], [`Parser::char`] and [`Parser::tag`] skip it.
+//!
+//! ```
+//! use aoc_common::parse::{ParseError, Parser};
+//!
+//! // `x,y -> x,y`
+//! let segments = Parser::new("0,9 -> 5,9\n8,0 -> 0,8\n").each_line(|line| {
+//!     let start = line.pair::<u16>(',')?;
+//!     line.tag("->")?;
+//!     Ok((start, line.pair::<u16>(',')?))
+//! });
+//! assert_eq!(segments, Ok(vec![((0, 9), (5, 9)), ((8, 0), (0, 8))]));
+//!
+//! let err = Parser::new("3,4,x").numbers::<u8>(',').unwrap_err();
+//! assert_eq!(err.to_string(), "line 1, column 5: expected a number, found 'x'");
+//! ```
+
+use std::{error, fmt, iter, ops::RangeInclusive, str::FromStr};
+
+use crate::SolveError;
+
+// longest excerpt of the input in an error message
+const MAX_FOUND: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.msg
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Parse(err.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Parser<'a> {
+    /// The whole input, for the positions of errors
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, rest: input }
+    }
+
+    /// Parser of `text`, a slice of the same input
+    fn of(&self, text: &'a str) -> Self {
+        Self {
+            input: self.input,
+            rest: text,
+        }
+    }
+
+    /// The text not parsed yet
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Nothing but whitespace left
+    pub fn is_empty(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    /// The non-empty lines without surrounding whitespace
+    pub fn lines(&self) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        self.rest
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(move |line| parser.of(line))
+    }
+
+    /// Blocks of lines separated by blank lines, e.g. passports or boards
+    pub fn records(&self) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        let mut lines = self.rest.lines().map(str::trim).peekable();
+        iter::from_fn(move || {
+            while lines.next_if(|line| line.is_empty()).is_some() {}
+            let first = lines.next()?;
+            let mut last = first;
+            while let Some(line) = lines.next_if(|line| !line.is_empty()) {
+                last = line;
+            }
+            let start = parser.offset(first);
+            let end = parser.offset(last) + last.len();
+            Some(parser.of(&parser.input[start..end]))
+        })
+    }
+
+    /// Items separated by `sep` without surrounding whitespace, none for an
+    /// empty text
+    pub fn split(&self, sep: char) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        let text = self.rest.trim();
+        text.split(sep)
+            .filter(move |_| !text.is_empty())
+            .map(move |item| parser.of(item.trim()))
+    }
+
+    /// Items separated by whitespace
+    pub fn words(&self) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        self.rest
+            .split_whitespace()
+            .map(move |word| parser.of(word))
+    }
+
+    /// `sep` separated numbers, or any whitespace for `' '`
+    pub fn numbers<T: FromStr>(&self, sep: char) -> Result<Vec<T>, ParseError> {
+        let number = |mut item: Parser<'a>| {
+            let number = item.number()?;
+            item.end()?;
+            Ok(number)
+        };
+        match sep {
+            ' ' => self.words().map(number).collect(),
+            _ => self.split(sep).map(number).collect(),
+        }
+    }
+
+    /// `key<sep>value` words like `hgt:183cm`
+    pub fn fields(&self, sep: char) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+        self.words()
+            .map(|word| {
+                word.rest.split_once(sep).ok_or_else(|| {
+                    word.error(format!("expected 'key{}value', found '{}'", sep, word.rest))
+                })
+            })
+            .collect()
+    }
+
+    /// Parses every line with `parse`, which has to consume the whole line
+    pub fn each_line<T>(
+        &self,
+        mut parse: impl FnMut(&mut Parser<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .map(|mut line| {
+                let value = parse(&mut line)?;
+                line.end()?;
+                Ok(value)
+            })
+            .collect()
+    }
+
+    /// Consumes `tag`, surrounding whitespace is skipped
+    pub fn tag(&mut self, tag: &str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(tag.trim()) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.expected(&format!("'{}'", tag.trim()))),
+        }
+    }
+
+    /// An integer with an optional sign
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        self.skip_whitespace();
+        let sign = usize::from(self.rest.starts_with(['-', '+']));
+        let len = sign
+            + self.rest[sign..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.rest.len() - sign);
+        if len == sign {
+            return Err(self.expected("a number"));
+        }
+        let (number, rest) = self.rest.split_at(len);
+        let value = number
+            .parse()
+            .map_err(|_| self.error(format!("number {} out of range", number)))?;
+        self.rest = rest;
+        Ok(value)
+    }
+
+    /// A number within `range`
+    pub fn number_in<T>(&mut self, range: RangeInclusive<T>) -> Result<T, ParseError>
+    where
+        T: FromStr + PartialOrd + fmt::Display,
+    {
+        let start = *self;
+        let value = self.number()?;
+        match range.contains(&value) {
+            true => Ok(value),
+            false => Err(start.error(format!(
+                "number {} not in {}..={}",
+                value,
+                range.start(),
+                range.end()
+            ))),
+        }
+    }
+
+    /// Two numbers separated by `sep`, e.g. `x,y`
+    pub fn pair<T: FromStr>(&mut self, sep: char) -> Result<(T, T), ParseError> {
+        let first = self.number()?;
+        self.tag(sep.encode_utf8(&mut [0; 4]))?;
+        Ok((first, self.number()?))
+    }
+
+    pub fn char(&mut self) -> Result<char, ParseError> {
+        self.skip_whitespace();
+        let mut chars = self.rest.chars();
+        let c = chars.next().ok_or_else(|| self.expected("a character"))?;
+        self.rest = chars.as_str();
+        Ok(c)
+    }
+
+    /// The text up to the next whitespace
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(char::is_whitespace)
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.expected("a word"));
+        }
+        let (word, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    /// The text up to `delim` without surrounding whitespace, `delim` is
+    /// consumed too
+    pub fn until(&mut self, delim: &str) -> Result<&'a str, ParseError> {
+        let (before, rest) = self
+            .rest
+            .split_once(delim)
+            .ok_or_else(|| self.expected(&format!("'{}'", delim)))?;
+        self.rest = rest;
+        Ok(before.trim())
+    }
+
+    /// The rest without surrounding whitespace
+    pub fn take_rest(&mut self) -> &'a str {
+        let rest = self.rest.trim();
+        self.rest = &self.rest[self.rest.len()..];
+        rest
+    }
+
+    /// Fails if anything but whitespace is left
+    pub fn end(&self) -> Result<(), ParseError> {
+        let mut parser = *self;
+        parser.skip_whitespace();
+        match parser.rest.is_empty() {
+            true => Ok(()),
+            false => Err(parser.error(format!("unexpected '{}'", parser.found()))),
+        }
+    }
+
+    /// Error at the next token
+    pub fn error(&self, msg: impl Into<String>) -> ParseError {
+        let before = &self.input[..self.offset(self.rest.trim_start())];
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count()
+                + 1,
+            msg: msg.into(),
+        }
+    }
+
+    fn expected(&self, what: &str) -> ParseError {
+        match self.rest.is_empty() {
+            true => self.error(format!("expected {}, found the end", what)),
+            false => self.error(format!("expected {}, found '{}'", what, self.found())),
+        }
+    }
+
+    /// The next word for error messages, shortened
+    fn found(&self) -> String {
+        self.rest
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(MAX_FOUND)
+            .collect()
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Offset of `part`, a slice of the input
+    fn offset(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.input.as_ptr() as usize);
+        match offset <= self.input.len() && self.input.is_char_boundary(offset) {
+            true => offset,
+            false => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_and_records() {
+        let input = "a b\n  c:d e:f \n\n\n   \ng\n";
+        let parser = Parser::new(input);
+        assert_eq!(
+            parser.lines().map(|line| line.rest()).collect::<Vec<_>>(),
+            ["a b", "c:d e:f", "g"]
+        );
+
+        let records = parser.records().collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].rest(), "a b\n  c:d e:f");
+        assert_eq!(records[1].rest(), "g");
+        assert_eq!(
+            records[0].fields(':').unwrap_err(),
+            ParseError {
+                line: 1,
+                column: 1,
+                msg: "expected 'key:value', found 'a'".to_string(),
+            }
+        );
+        let mut second_line = records[0].lines().nth(1).unwrap();
+        assert_eq!(second_line.fields(':'), Ok(vec![("c", "d"), ("e", "f")]));
+        assert_eq!(second_line.word(), Ok("c:d"));
+        assert_eq!(second_line.error("x").column, 7);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let parser = Parser::new("3,4, 3,1,2\n");
+        assert_eq!(parser.numbers::<u8>(','), Ok(vec![3, 4, 3, 1, 2]));
+        assert_eq!(
+            Parser::new(" 8  2 -23\n").numbers::<i8>(' '),
+            Ok(vec![8, 2, -23])
+        );
+        assert_eq!(Parser::new("\n").numbers::<u8>(','), Ok(vec![]));
+
+        let second_line = Parser::new("1,2\n3,300").lines().nth(1).unwrap();
+        let err = second_line.numbers::<u8>(',').unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: number 300 out of range");
+        let err = Parser::new("1,2,").numbers::<u8>(',').unwrap_err();
+        assert_eq!(err.msg, "expected a number, found the end");
+        let err = Parser::new("1,2x").numbers::<u8>(',').unwrap_err();
+        assert_eq!((err.column, err.msg.as_str()), (4, "unexpected 'x'"));
+
+        let err = Parser::new("9,5").number_in(0..=8u8).unwrap_err();
+        assert_eq!(err.msg, "number 9 not in 0..=8");
+        let mut parser = Parser::new("7, 9");
+        assert_eq!(parser.number_in(0..=8), Ok(7));
+        parser.tag(",").unwrap();
+        assert_eq!(parser.number_in(0..=8).unwrap_err().column, 4);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let policies = Parser::new("1-3 a: abcde\n  2-9 c: ccccccccc").each_line(|line| {
+            let min = line.number::<usize>()?;
+            line.tag("-")?;
+            let max = line.number::<usize>()?;
+            let letter = line.char()?;
+            line.tag(":")?;
+            Ok((min, max, letter, line.take_rest()))
+        });
+        assert_eq!(
+            policies,
+            Ok(vec![(1, 3, 'a', "abcde"), (2, 9, 'c', "ccccccccc")])
+        );
+
+        let mut fold = Parser::new("fold along y=7");
+        assert_eq!(fold.tag("fold along"), Ok(()));
+        assert_eq!(fold.until("="), Ok("y"));
+        assert_eq!(fold.number::<u16>(), Ok(7));
+        assert!(fold.is_empty());
+        assert_eq!(
+            fold.char().unwrap_err().msg,
+            "expected a character, found the end"
+        );
+
+        let err = Parser::new("0,9 -> 5,9\n8,0 => 0,8")
+            .each_line(|line| {
+                let start = line.pair::<u16>(',')?;
+                line.tag("->")?;
+                Ok((start, line.pair::<u16>(',')?))
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: expected '->', found '=>'"
+        );
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let err = Parser::new("\n\tx")
+            .word()
+            .map(|_| ())
+            .and(Parser::new("\n\tx").end());
+        assert_eq!(
+            SolveError::from(err.unwrap_err()),
+            SolveError::Parse("line 2, column 2: unexpected 'x'".to_string())
+        );
+    }
+}
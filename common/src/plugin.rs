@@ -0,0 +1,419 @@
+//! Solvers loaded at runtime from shared libraries, e.g. experimental
+//! solutions of teammates, without rebuilding the runner.
+//!
+//! This is the only module with unsafe code, on both sides of the interface.
+//! The interface is plain C, so plugins don't have to be built by the same
+//! compiler as the runner:
+//!
+//! - `aoc_plugin_api_version() -> u32` is checked against [`API_VERSION`]
+//!   before anything else is read from the library
+//! - `aoc_plugin_declaration() -> Declaration` names the day and the function
+//!   solving a part
+//!
+//! Inputs are passed as UTF-8, the parameters and the results as JSON. A day
+//! crate becomes a plugin with `crate-type = ["cdylib", "rlib"]` and
+//!
+//! ```ignore
+//! aoc_common::export_plugin!(Day);
+//! ```
+
+#![allow(unsafe_code)]
+
+use std::{
+    collections::BTreeMap,
+    env::consts::DLL_EXTENSION,
+    error,
+    ffi::{c_char, c_void, CStr, CString},
+    fmt, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    slice, str,
+};
+
+use libloading::{Library, Symbol};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{run, Abort, Answer, Config, Solution, SolveError};
+
+/// Changes whenever [`Declaration`] or the JSON it exchanges changes
+pub const API_VERSION: u32 = 1;
+
+const VERSION_SYMBOL: &[u8] = b"aoc_plugin_api_version\0";
+const DECLARATION_SYMBOL: &[u8] = b"aoc_plugin_declaration\0";
+
+/// Solves part `part` of `input` with the parameters as a JSON object, the
+/// result is `{"answer": ...}` or `{"error": ...}` and freed by [`FreeFn`]
+pub type SolveFn = unsafe extern "C" fn(
+    solver: *const c_void,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const u8,
+    params_len: usize,
+) -> *mut c_char;
+
+pub type FreeFn = unsafe extern "C" fn(result: *mut c_char);
+
+/// What a plugin exports, created by [`export_plugin!`]
+#[repr(C)]
+pub struct Declaration {
+    pub year: u16,
+    pub day: u8,
+    /// Handed back to `solve`
+    pub solver: *const c_void,
+    pub solve: SolveFn,
+    pub free: FreeFn,
+}
+
+/// Exports `$solution`, a `Solution` constant like `Day`, from a plugin
+#[macro_export]
+macro_rules! export_plugin {
+    ($solution:expr) => {
+        #[allow(unsafe_code)]
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_api_version() -> u32 {
+            $crate::plugin::API_VERSION
+        }
+
+        #[allow(unsafe_code)]
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_declaration() -> $crate::plugin::Declaration {
+            $crate::plugin::declare(&$solution)
+        }
+    };
+}
+
+/// Plugin side of [`export_plugin!`]
+pub fn declare(solution: &'static dyn Solution) -> Declaration {
+    Declaration {
+        year: solution.year(),
+        day: solution.day(),
+        // a thin pointer to the trait object, leaked once per load
+        solver: Box::into_raw(Box::new(solution)) as *const c_void,
+        solve: plugin_solve,
+        free: plugin_free,
+    }
+}
+
+/// # Safety
+///
+/// `solver` comes from [`declare`], `input` and `params` point to
+/// `input_len` and `params_len` readable bytes.
+unsafe extern "C" fn plugin_solve(
+    solver: *const c_void,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const u8,
+    params_len: usize,
+) -> *mut c_char {
+    let solution = *(solver as *const &'static dyn Solution);
+    let input = slice::from_raw_parts(input, input_len);
+    let params = slice::from_raw_parts(params, params_len);
+
+    // a panic must not unwind into the runner
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let input = str::from_utf8(input)
+            .map_err(|_| SolveError::Parse("the input isn't UTF-8".to_string()))?;
+        let mut config = Config::new(solution.year(), solution.day());
+        config.params = serde_json::from_slice::<BTreeMap<String, String>>(params)
+            .map_err(|err| SolveError::Parse(format!("parameters: {}", err)))?;
+        solution.solve(part, input, &config)
+    }))
+    .unwrap_or_else(|payload| {
+        Err(SolveError::Aborted(Abort::Panic(run::panic_message(
+            &*payload,
+        ))))
+    });
+
+    let reply = match result {
+        Ok(answer) => json!({ "answer": answer }),
+        Err(err) => json!({ "error": err }),
+    };
+    // JSON escapes NUL, so this can't fail
+    CString::new(reply.to_string())
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// # Safety
+///
+/// `result` comes from `plugin_solve` and is freed only once.
+unsafe extern "C" fn plugin_free(result: *mut c_char) {
+    if !result.is_null() {
+        drop(CString::from_raw(result));
+    }
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    Load(String),
+    /// The plugin was built for another version of the interface
+    Version(u32),
+    InvalidDay(u16, u8),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::Load(msg) => write!(f, "can't load plugin: {}", msg),
+            PluginError::Version(found) => write!(
+                f,
+                "plugin interface version {}, the runner has version {}, rebuild the plugin",
+                found, API_VERSION
+            ),
+            PluginError::InvalidDay(year, day) => {
+                write!(f, "plugin declares the invalid day {}/{:02}", year, day)
+            }
+        }
+    }
+}
+
+impl error::Error for PluginError {}
+
+/// A loaded plugin, solving its day like any other [`Solution`]
+pub struct Plugin {
+    path: PathBuf,
+    declaration: Declaration,
+    // the code `declaration` points into, unloaded last
+    _library: Library,
+}
+
+// SAFETY: `solver` is a `&'static dyn Solution` and `Solution: Sync`, the
+// exported functions only read it
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let load_error = |err: libloading::Error| PluginError::Load(err.to_string());
+
+        // SAFETY: loading runs the library's initializers, plugins are trusted
+        // code like the solvers built into the runner
+        let library = unsafe { Library::new(path) }.map_err(load_error)?;
+
+        // SAFETY: the signatures are those of `export_plugin!`, the version
+        // is checked before the declaration of that version is read
+        let declaration = unsafe {
+            let version: Symbol<extern "C" fn() -> u32> =
+                library.get(VERSION_SYMBOL).map_err(load_error)?;
+            check_version(version())?;
+            let declare: Symbol<extern "C" fn() -> Declaration> =
+                library.get(DECLARATION_SYMBOL).map_err(load_error)?;
+            declare()
+        };
+        check_day(declaration.year, declaration.day)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            declaration,
+            _library: library,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn call(&self, part: u8, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        let params = serde_json::to_vec(&config.params).unwrap_or_default();
+
+        // SAFETY: the pointers are valid for the lengths passed, the result
+        // is a NUL terminated string freed by the plugin right after copying
+        let reply = unsafe {
+            let result = (self.declaration.solve)(
+                self.declaration.solver,
+                part,
+                input.as_ptr(),
+                input.len(),
+                params.as_ptr(),
+                params.len(),
+            );
+            if result.is_null() {
+                return Err(SolveError::Parse(
+                    "the plugin returned no result".to_string(),
+                ));
+            }
+            let reply = CStr::from_ptr(result).to_string_lossy().into_owned();
+            (self.declaration.free)(result);
+            reply
+        };
+
+        parse_reply(&reply)
+    }
+}
+
+impl Solution for Plugin {
+    fn year(&self) -> u16 {
+        self.declaration.year
+    }
+
+    fn day(&self) -> u8 {
+        self.declaration.day
+    }
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        self.call(1, input, config)
+    }
+
+    fn part2(&self, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        self.call(2, input, config)
+    }
+}
+
+fn check_version(found: u32) -> Result<(), PluginError> {
+    match found == API_VERSION {
+        true => Ok(()),
+        false => Err(PluginError::Version(found)),
+    }
+}
+
+fn check_day(year: u16, day: u8) -> Result<(), PluginError> {
+    match year >= 2015 && (1..=25).contains(&day) {
+        true => Ok(()),
+        false => Err(PluginError::InvalidDay(year, day)),
+    }
+}
+
+fn parse_reply(reply: &str) -> Result<Answer, SolveError> {
+    #[derive(Deserialize)]
+    struct Reply {
+        answer: Option<Answer>,
+        error: Option<SolveError>,
+    }
+
+    match serde_json::from_str::<Reply>(reply) {
+        Ok(Reply {
+            answer: Some(answer),
+            ..
+        }) => Ok(answer),
+        Ok(Reply {
+            error: Some(err), ..
+        }) => Err(err),
+        _ => Err(SolveError::Parse(format!(
+            "invalid reply of the plugin: {}",
+            reply
+        ))),
+    }
+}
+
+/// Loads the shared libraries in `dir`, sorted by file name, a missing
+/// directory has no plugins
+pub fn discover(dir: &Path) -> Vec<(PathBuf, Result<Plugin, PluginError>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == DLL_EXTENSION))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let plugin = Plugin::load(&path);
+            (path, plugin)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+
+    /// Builds the fixture plugin, a solver of 2015/01
+    fn build_fixture() -> PathBuf {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/plugin");
+        let target_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/plugin-fixture");
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let status = Command::new(cargo)
+            .args(["build", "-q", "--manifest-path"])
+            .arg(fixture.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let name = format!(
+            "{}aoc_plugin_fixture.{}",
+            std::env::consts::DLL_PREFIX,
+            DLL_EXTENSION
+        );
+        target_dir.join("debug").join(name)
+    }
+
+    #[test]
+    fn test_plugin() {
+        let plugin = Plugin::load(&build_fixture()).unwrap();
+        assert_eq!((plugin.year(), plugin.day()), (2015, 1));
+
+        let config = Config::new(2015, 1);
+        assert_eq!(plugin.part1("(()(()(", &config), Ok(Answer::from(3)));
+        assert_eq!(plugin.part1("))(((((", &config), Ok(Answer::from(3)));
+        assert_eq!(plugin.part2("()())", &config), Ok(Answer::from(5)));
+        assert_eq!(
+            plugin.part2("((", &config),
+            Err(SolveError::NoSolution("never in the basement".to_string()))
+        );
+        assert_eq!(
+            plugin.solve(3, "(", &config),
+            Err(SolveError::InvalidPart(3))
+        );
+
+        let mut config = config;
+        config.params.insert("floor".to_string(), "10".to_string());
+        assert_eq!(plugin.part1("((", &config), Ok(Answer::from(12)));
+
+        // a panic of the plugin is an error of the part
+        assert!(matches!(
+            plugin.part1("(x", &Config::new(2015, 1)),
+            Err(SolveError::Aborted(Abort::Panic(msg))) if !msg.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_checks() {
+        assert!(check_version(API_VERSION).is_ok());
+        assert_eq!(
+            check_version(API_VERSION + 1).unwrap_err().to_string(),
+            format!(
+                "plugin interface version {}, the runner has version {}, rebuild the plugin",
+                API_VERSION + 1,
+                API_VERSION
+            )
+        );
+        assert!(check_day(2021, 25).is_ok());
+        assert!(check_day(2021, 26).is_err());
+        assert!(check_day(1999, 1).is_err());
+
+        assert_eq!(parse_reply(r#"{"answer":42}"#), Ok(Answer::from(42)));
+        assert_eq!(
+            parse_reply(r#"{"error":"NotImplemented"}"#),
+            Err(SolveError::NotImplemented)
+        );
+        assert!(parse_reply("{}").is_err());
+    }
+
+    #[test]
+    fn test_discover() {
+        assert!(discover(Path::new("/nonexistent")).is_empty());
+
+        let dir = std::env::temp_dir().join(format!("aoc-plugins-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("broken.{}", DLL_EXTENSION)),
+            b"not a library",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+        let found = discover(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert!(matches!(found[0].1, Err(PluginError::Load(_))));
+    }
+}
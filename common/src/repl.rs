@@ -0,0 +1,60 @@
+//! Commands a day exposes to `aoc repl` for exploring its parsed input.
+//!
+//! A [`Command`] calls one of the day's private functions on the loaded input
+//! and renders the result, usually with `{:#?}` of the intermediate value.
+
+use std::str::FromStr;
+
+use crate::config::Config;
+
+/// Input, the arguments after the command name and the configuration
+pub type CommandFn = fn(&str, &[&str], &Config) -> Result<String, String>;
+
+#[derive(Clone, Copy)]
+pub struct Command {
+    pub name: &'static str,
+    /// Arguments for the help, e.g. `<right> <down>`
+    pub args: &'static str,
+    pub help: &'static str,
+    pub run: CommandFn,
+}
+
+impl Command {
+    pub fn new(name: &'static str, args: &'static str, help: &'static str, run: CommandFn) -> Self {
+        Self {
+            name,
+            args,
+            help,
+            run,
+        }
+    }
+}
+
+/// Parses the argument at `idx`, the error names it by `name`
+pub fn arg<T: FromStr>(args: &[&str], idx: usize, name: &str) -> Result<T, String> {
+    let value = args
+        .get(idx)
+        .ok_or_else(|| format!("missing argument <{}>", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid <{}> '{}'", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arg() {
+        let args = ["5", "x"];
+        assert_eq!(arg::<usize>(&args, 0, "right"), Ok(5));
+        assert_eq!(
+            arg::<usize>(&args, 1, "down"),
+            Err("invalid <down> 'x'".to_string())
+        );
+        assert_eq!(
+            arg::<usize>(&args, 2, "pos"),
+            Err("missing argument <pos>".to_string())
+        );
+    }
+}
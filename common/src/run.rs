@@ -0,0 +1,370 @@
+//! Running the parts of a [`Solution`] and printing the answers.
+//!
+//! Text output goes to stdout one part per line, JSON output is one object per
+//! line so results of several days can be concatenated.
+
+use std::{
+    any::Any,
+    fmt, process,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{info_span, warn};
+
+use crate::{
+    answer::Answer,
+    answers::StoredAnswers,
+    config::{Config, Format},
+    input::read_input,
+    logging,
+    solution::{Abort, Solution, SolveError},
+};
+
+/// Heap usage of one part, measured by the runner's counting allocator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Peak resident set size of the process, where the OS reports it
+    pub peak_rss: Option<u64>,
+}
+
+impl fmt::Display for MemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}",
+            self.allocations,
+            format_bytes(self.bytes)
+        )?;
+        if let Some(peak_rss) = self.peak_rss {
+            write!(f, ", peak RSS {}", format_bytes(peak_rss))?;
+        }
+        Ok(())
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartResult {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Named input the part ran on, `None` for the default input
+    #[serde(default)]
+    pub user: Option<String>,
+    pub answer: Result<Answer, SolveError>,
+    pub elapsed: Duration,
+    /// Stored answer for the same input, if any
+    pub expected: Option<Answer>,
+    /// Only set when the runner is built with allocation counting
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    /// Result of a part whose process didn't finish
+    pub fn aborted(year: u16, day: u8, part: u8, abort: Abort, elapsed: Duration) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            user: None,
+            answer: Err(SolveError::Aborted(abort)),
+            elapsed,
+            expected: None,
+            memory: None,
+        }
+    }
+
+    /// `None` without a stored answer
+    pub fn is_correct(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| self.answer.as_ref() == Ok(expected))
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (Some(answer), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "user": self.user,
+            "answer": answer,
+            "error": error,
+            "elapsed_ns": self.elapsed.as_nanos() as u64,
+            "expected": self.expected,
+            "correct": self.is_correct(),
+            "allocations": self.memory.map(|memory| memory.allocations),
+            "allocated_bytes": self.memory.map(|memory| memory.bytes),
+            "peak_rss_bytes": self.memory.and_then(|memory| memory.peak_rss),
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        let label = match &self.user {
+            Some(user) => format!(
+                "{}/{:02} [{}] part {}",
+                self.year, self.day, user, self.part
+            ),
+            None => format!("{}/{:02} part {}", self.year, self.day, self.part),
+        };
+        let stats = match &self.memory {
+            Some(memory) => format!("{:?}, {}", self.elapsed, memory),
+            None => format!("{:?}", self.elapsed),
+        };
+        let text = match &self.answer {
+            // grids start on their own line
+            Ok(Answer::Grid(grid)) => format!("{} ({}):\n{}", label, stats, grid),
+            Ok(answer) => format!("{}: {} ({})", label, answer, stats),
+            Err(err) => format!("{}: error: {} ({})", label, err, stats),
+        };
+        match (&self.expected, self.is_correct()) {
+            (Some(Answer::Grid(grid)), Some(false)) => format!("{}\n  expected:\n{}", text, grid),
+            (Some(expected), Some(false)) => format!("{}\n  expected: {}", text, expected),
+            _ => text,
+        }
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => println!("{}", self.to_text()),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+}
+
+/// The message of a caught panic, `panic!` payloads are a `&str` or a
+/// `String`
+pub fn panic_message(payload: &dyn Any) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+pub fn run_part(solution: &dyn Solution, part: u8, input: &str, config: &Config) -> PartResult {
+    let _part = info_span!("part", n = part).entered();
+    let start = Instant::now();
+    let answer = solution.solve(part, input, config);
+
+    PartResult {
+        year: solution.year(),
+        day: solution.day(),
+        part,
+        user: config.user.clone(),
+        answer,
+        elapsed: start.elapsed(),
+        expected: None,
+        memory: None,
+    }
+}
+
+/// Runs the configured parts of one day
+pub fn run_day(solution: &dyn Solution, input: &str, config: &Config) -> Vec<PartResult> {
+    run_day_with(config, |part| run_part(solution, part, input, config))
+}
+
+/// Like [`run_day`] with `run` wrapping [`run_part`], e.g. to measure it
+pub fn run_day_with(config: &Config, run: impl FnMut(u8) -> PartResult) -> Vec<PartResult> {
+    let _day = logging::day_span(config).entered();
+    config.parts.iter().copied().map(run).collect()
+}
+
+/// Warns if `input` doesn't look like the day's input, other days which
+/// accept it are named as likely owners
+pub fn sniff_input(solution: &dyn Solution, input: &str, others: &[&dyn Solution]) -> bool {
+    if solution.sniff(input) {
+        return true;
+    }
+
+    let candidates = others
+        .iter()
+        .filter(|other| other.sniff(input))
+        .map(|other| format!("{}/{:02}", other.year(), other.day()))
+        .collect::<Vec<_>>();
+    warn!(
+        year = solution.year(),
+        day = solution.day(),
+        looks_like = %candidates.join(", "),
+        "input doesn't look like the input of this day"
+    );
+    false
+}
+
+/// Sets the expected answers if `stored` was recorded for `input`
+pub fn verify(results: &mut [PartResult], stored: &StoredAnswers, input: &str) -> bool {
+    if !stored.is_for(input) {
+        if let Some(result) = results.first() {
+            warn!(
+                year = result.year,
+                day = result.day,
+                "input differs from the one the stored answers were recorded for"
+            );
+        }
+        return false;
+    }
+
+    results
+        .iter_mut()
+        .for_each(|result| result.expected = stored.get(result.part).cloned());
+    true
+}
+
+/// Solved and matching the stored answer, if there is one
+pub fn is_success(result: &PartResult) -> bool {
+    result.answer.is_ok() && result.is_correct() != Some(false)
+}
+
+/// Entry point of the day binaries
+pub fn main(solution: &dyn Solution) {
+    let config = Config::load(solution.year(), solution.day()).expect("invalid configuration");
+    logging::init(&config).expect("invalid configuration");
+
+    let path = config.input_path();
+    let input = read_input(&path).unwrap_or_else(|err| {
+        eprintln!("{}: {}", path.display(), err);
+        process::exit(1);
+    });
+
+    sniff_input(solution, &input, &[]);
+    let mut results = run_day(solution, &input, &config);
+    match StoredAnswers::load(&config.answers_path()) {
+        Ok(Some(stored)) => {
+            verify(&mut results, &stored, &input);
+        }
+        Ok(None) => {}
+        Err(err) => warn!("{}", err),
+    }
+    results
+        .iter()
+        .for_each(|result| result.print(config.format));
+
+    if !results.iter().all(is_success) {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        fn year(&self) -> u16 {
+            2021
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn part1(&self, input: &str, _config: &Config) -> Result<Answer, SolveError> {
+            Ok(Answer::from(input.lines().count()))
+        }
+
+        fn part2(&self, _input: &str, _config: &Config) -> Result<Answer, SolveError> {
+            Err(SolveError::NoSolution("example".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_run_day() {
+        let mut config = Config::new(2021, 1);
+        let results = run_day(&Example, "1\n2\n3", &config);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Ok(Answer::from(3u8)));
+        assert!(results[1].answer.is_err());
+
+        config.parts = vec![2];
+        let results = run_day(&Example, "1\n2\n3", &config);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].part, 2);
+    }
+
+    #[test]
+    fn test_output() {
+        let mut result = run_part(&Example, 1, "1\n2", &Config::new(2021, 1));
+        result.elapsed = Duration::from_millis(2);
+        assert_eq!(result.to_text(), "2021/01 part 1: 2 (2ms)");
+
+        let json = result.to_json();
+        assert_eq!(json["answer"], 2);
+        assert_eq!(json["error"], serde_json::Value::Null);
+        assert_eq!(json["elapsed_ns"], 2_000_000);
+
+        result.expected = Some(Answer::from(3u8));
+        assert_eq!(result.to_text(), "2021/01 part 1: 2 (2ms)\n  expected: 3");
+        assert_eq!(result.to_json()["correct"], false);
+
+        result.expected = None;
+        result.memory = Some(MemoryStats {
+            allocations: 3,
+            bytes: 1536,
+            peak_rss: Some(5 << 20),
+        });
+        assert_eq!(
+            result.to_text(),
+            "2021/01 part 1: 2 (2ms, 3 allocs, 1.5 KiB, peak RSS 5.0 MiB)"
+        );
+        assert_eq!(result.to_json()["allocated_bytes"], 1536);
+
+        let mut result = run_part(&Example, 1, "1\n2", &Config::new(2021, 1).for_user("alice"));
+        result.elapsed = Duration::from_millis(2);
+        assert_eq!(result.to_text(), "2021/01 [alice] part 1: 2 (2ms)");
+        assert_eq!(result.to_json()["user"], "alice");
+
+        let result = run_part(&Example, 3, "", &Config::new(2021, 1));
+        assert_eq!(result.answer, Err(SolveError::InvalidPart(3)));
+        assert_eq!(result.to_json()["allocations"], serde_json::Value::Null);
+        assert!(result.to_text().contains("error: invalid part 3"));
+    }
+
+    #[test]
+    fn test_verify() {
+        let config = Config::new(2021, 1);
+        let mut results = run_day(&Example, "1\n2", &config);
+
+        let mut stored = StoredAnswers::new("1\n2\n3");
+        stored.set(1, Answer::from(2u8));
+        assert!(!verify(&mut results, &stored, "1\n2"));
+        assert_eq!(results[0].is_correct(), None);
+
+        let mut stored = StoredAnswers::new("1\n2");
+        stored.set(1, Answer::from(2u8));
+        assert!(verify(&mut results, &stored, "1\n2"));
+        assert_eq!(results[0].is_correct(), Some(true));
+        assert!(is_success(&results[0]));
+        assert_eq!(results[1].is_correct(), None);
+        assert!(!is_success(&results[1]));
+    }
+
+    #[test]
+    fn test_grid_output() {
+        let mut result = run_part(&Example, 1, "", &Config::new(2021, 1));
+        result.answer = Ok("X  X\nXXXX".parse().unwrap());
+        result.elapsed = Duration::from_micros(5);
+        result.expected = Some("X  X\nX  X".parse().unwrap());
+        crate::assert_snapshot!("grid_output", result.to_text());
+    }
+}
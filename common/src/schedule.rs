@@ -0,0 +1,399 @@
+//! When puzzles unlock and how often the site may be asked for them.
+//!
+//! Puzzles unlock at midnight US Eastern time. A [`Scheduler`] refuses to
+//! fetch a puzzle before then and keeps a minimum interval between requests,
+//! remembering the last one in a file so separate runs share the budget. Time
+//! comes from a [`Clock`], the system one or a [`FakeClock`] in tests.
+//!
+//! ```
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! use aoc_common::schedule;
+//!
+//! // 2021-12-01 05:00 UTC
+//! let unlock = schedule::unlock_time(2021, 1).unwrap();
+//! assert_eq!(unlock, UNIX_EPOCH + Duration::from_secs(1_638_334_800));
+//! assert!(schedule::unlock_time(2021, 26).is_err());
+//! ```
+
+use std::{
+    cell::Cell,
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tracing::debug;
+
+use crate::config;
+
+pub const FIRST_YEAR: u16 = 2015;
+/// Between two requests, unless set with [`Scheduler::min_interval`]
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+const SECS_PER_DAY: i64 = 24 * SECS_PER_HOUR;
+
+#[derive(Debug)]
+pub enum ScheduleError {
+    /// No such puzzle
+    InvalidDay(u16, u8),
+    /// The puzzle unlocks in the given time
+    Locked(u16, u8, Duration),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::InvalidDay(year, day) => write!(f, "no puzzle {}/{:02}", year, day),
+            ScheduleError::Locked(year, day, remaining) => {
+                write!(f, "{}/{:02} unlocks in {}s", year, day, remaining.as_secs())
+            }
+            ScheduleError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl error::Error for ScheduleError {}
+
+/// Source of the current time
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Clock that only moves when told to, sleeping advances it at once
+#[derive(Debug)]
+pub struct FakeClock {
+    now: Cell<SystemTime>,
+}
+
+impl FakeClock {
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/// Number of puzzles in the event of `year`
+pub fn days_in(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Midnight US Eastern time on December `day` of `year`
+pub fn unlock_time(year: u16, day: u8) -> Result<SystemTime, ScheduleError> {
+    if year < FIRST_YEAR || !(1..=days_in(year)).contains(&day) {
+        return Err(ScheduleError::InvalidDay(year, day));
+    }
+    let local = days_from_civil(year.into(), 12, day.into()) * SECS_PER_DAY;
+    let utc = local - eastern_offset(year.into(), 12, day.into()) * SECS_PER_HOUR;
+    Ok(UNIX_EPOCH + Duration::from_secs(utc as u64))
+}
+
+/// Hours from UTC to US Eastern time at midnight of the date, -4 in daylight
+/// saving time and -5 otherwise
+pub fn eastern_offset(year: i64, month: u32, day: u32) -> i64 {
+    // the clocks change at 2:00, midnight of the day itself is still before
+    let (start, end) = if year >= 2007 {
+        // second Sunday of March to first Sunday of November
+        (nth_sunday(year, 3, 2), nth_sunday(year, 11, 1))
+    } else {
+        // first Sunday of April to last Sunday of October
+        (nth_sunday(year, 4, 1), nth_sunday(year, 11, 1) - 7)
+    };
+    let date = days_from_civil(year, month, day);
+    if start < date && date <= end {
+        -4
+    } else {
+        -5
+    }
+}
+
+// days since 1970-01-01 of the `n`th Sunday of the month
+fn nth_sunday(year: i64, month: u32, n: i64) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    // 1970-01-01 was a Thursday, 3 days before a Sunday
+    let until_sunday = (3 - first).rem_euclid(7);
+    first + until_sunday + 7 * (n - 1)
+}
+
+// days since 1970-01-01 of a date in the proleptic Gregorian calendar, after
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// `last_request` in the user config directory, next to the session token
+pub fn default_state_file() -> PathBuf {
+    config::user_config_dir().join("last_request")
+}
+
+/// Gate for the requests to the site
+pub struct Scheduler<C: Clock = SystemClock> {
+    clock: C,
+    min_interval: Duration,
+    /// Holds the time of the last request in milliseconds since the epoch
+    state_file: PathBuf,
+    last_request: Option<SystemTime>,
+}
+
+impl Scheduler {
+    /// Scheduler on the system clock remembering requests in
+    /// [`default_state_file`]
+    pub fn new() -> Result<Self, ScheduleError> {
+        Self::with_clock(SystemClock, default_state_file())
+    }
+}
+
+impl<C: Clock> Scheduler<C> {
+    /// Picks up the last request from `state_file` if it exists
+    pub fn with_clock(clock: C, state_file: PathBuf) -> Result<Self, ScheduleError> {
+        let last_request = read_last_request(&state_file)?;
+        Ok(Self {
+            clock,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            state_file,
+            last_request,
+        })
+    }
+
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn last_request(&self) -> Option<SystemTime> {
+        self.last_request
+    }
+
+    /// Time until the puzzle unlocks, zero once it has
+    pub fn unlocks_in(&self, year: u16, day: u8) -> Result<Duration, ScheduleError> {
+        let unlock = unlock_time(year, day)?;
+        Ok(unlock
+            .duration_since(self.clock.now())
+            .unwrap_or(Duration::ZERO))
+    }
+
+    pub fn check_unlocked(&self, year: u16, day: u8) -> Result<(), ScheduleError> {
+        match self.unlocks_in(year, day)? {
+            Duration::ZERO => Ok(()),
+            remaining => Err(ScheduleError::Locked(year, day, remaining)),
+        }
+    }
+
+    /// Time until the next request may go out
+    pub fn wait_time(&self) -> Duration {
+        let Some(last) = self.last_request else {
+            return Duration::ZERO;
+        };
+        // a clock set back counts as no time elapsed
+        let elapsed = self
+            .clock
+            .now()
+            .duration_since(last)
+            .unwrap_or(Duration::ZERO);
+        self.min_interval.saturating_sub(elapsed)
+    }
+
+    /// Waits for the minimum interval since the last request and records a
+    /// new one
+    pub fn throttle(&mut self) -> Result<(), ScheduleError> {
+        let wait = self.wait_time();
+        if !wait.is_zero() {
+            debug!(?wait, "throttling request");
+            self.clock.sleep(wait);
+        }
+        let now = self.clock.now();
+        write_last_request(&self.state_file, now)?;
+        self.last_request = Some(now);
+        Ok(())
+    }
+
+    /// Refuses a puzzle that isn't unlocked yet, otherwise throttles
+    pub fn before_fetch(&mut self, year: u16, day: u8) -> Result<(), ScheduleError> {
+        self.check_unlocked(year, day)?;
+        self.throttle()
+    }
+}
+
+fn read_last_request(path: &Path) -> Result<Option<SystemTime>, ScheduleError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(ScheduleError::Io(path.to_path_buf(), err)),
+    };
+    let millis = contents.trim().parse::<u64>().map_err(|err| {
+        ScheduleError::Io(
+            path.to_path_buf(),
+            io::Error::new(io::ErrorKind::InvalidData, err),
+        )
+    })?;
+    Ok(Some(UNIX_EPOCH + Duration::from_millis(millis)))
+}
+
+fn write_last_request(path: &Path, time: SystemTime) -> Result<(), ScheduleError> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| ScheduleError::Io(dir.to_path_buf(), err))?;
+    }
+    fs::write(path, format!("{}\n", millis))
+        .map_err(|err| ScheduleError::Io(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn state_file(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-schedule-{}", std::process::id()));
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2015-12-01 05:00 UTC
+        assert_eq!(unlock_time(2015, 1).unwrap(), at(1_448_946_000));
+        // 2021-12-25 05:00 UTC
+        assert_eq!(unlock_time(2021, 25).unwrap(), at(1_640_408_400));
+        assert_eq!(unlock_time(2025, 12).unwrap(), at(1_765_515_600));
+
+        for (year, day) in [(2014, 1), (2021, 0), (2021, 26), (2025, 13)] {
+            assert!(matches!(
+                unlock_time(year, day),
+                Err(ScheduleError::InvalidDay(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_eastern_offset() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        // 2021: March 14 to November 7
+        assert_eq!(eastern_offset(2021, 3, 14), -5);
+        assert_eq!(eastern_offset(2021, 3, 15), -4);
+        assert_eq!(eastern_offset(2021, 11, 7), -4);
+        assert_eq!(eastern_offset(2021, 11, 8), -5);
+        // 2006: April 2 to October 29
+        assert_eq!(eastern_offset(2006, 3, 20), -5);
+        assert_eq!(eastern_offset(2006, 4, 3), -4);
+        assert_eq!(eastern_offset(2006, 10, 29), -4);
+        assert_eq!(eastern_offset(2006, 10, 30), -5);
+    }
+
+    #[test]
+    fn test_locked() {
+        let unlock = unlock_time(2021, 5).unwrap();
+        let clock = FakeClock::new(unlock - Duration::from_secs(90));
+        let mut scheduler = Scheduler::with_clock(clock, state_file("locked")).unwrap();
+
+        match scheduler.before_fetch(2021, 5) {
+            Err(ScheduleError::Locked(2021, 5, remaining)) => {
+                assert_eq!(remaining, Duration::from_secs(90))
+            }
+            other => panic!("expected locked, got {:?}", other),
+        }
+        // a refused fetch isn't a request
+        assert_eq!(scheduler.last_request(), None);
+
+        scheduler.clock().set(unlock);
+        scheduler.before_fetch(2021, 5).unwrap();
+        assert_eq!(scheduler.last_request(), Some(unlock));
+        assert!(scheduler.check_unlocked(2021, 4).is_ok());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let path = state_file("throttle");
+        let clock = FakeClock::new(at(1_000));
+        let mut scheduler = Scheduler::with_clock(clock, path.clone())
+            .unwrap()
+            .min_interval(Duration::from_secs(60));
+
+        assert_eq!(scheduler.wait_time(), Duration::ZERO);
+        scheduler.throttle().unwrap();
+        assert_eq!(scheduler.clock().now(), at(1_000));
+
+        scheduler.clock().advance(Duration::from_secs(20));
+        assert_eq!(scheduler.wait_time(), Duration::from_secs(40));
+        scheduler.throttle().unwrap();
+        assert_eq!(scheduler.clock().now(), at(1_060));
+        assert_eq!(scheduler.last_request(), Some(at(1_060)));
+
+        // a clock set back waits the whole interval
+        scheduler.clock().set(at(500));
+        assert_eq!(scheduler.wait_time(), Duration::from_secs(60));
+
+        // a later run picks up the last request
+        let scheduler = Scheduler::with_clock(FakeClock::new(at(1_100)), path.clone())
+            .unwrap()
+            .min_interval(Duration::from_secs(60));
+        assert_eq!(scheduler.last_request(), Some(at(1_060)));
+        assert_eq!(scheduler.wait_time(), Duration::from_secs(20));
+
+        fs::write(&path, "soon").unwrap();
+        assert!(matches!(
+            Scheduler::with_clock(FakeClock::new(at(0)), path),
+            Err(ScheduleError::Io(..))
+        ));
+    }
+}
@@ -0,0 +1,260 @@
+//! Stepped simulations: octopus flashes, fish populations, cellular
+//! automata.
+//!
+//! A day implements [`Simulation`] for its state and a [`Runner`] steps it.
+//! The runner is an iterator over what every step reports, so the usual
+//! `sum`, `take` and `position` apply, and it stops at an optional step
+//! limit. States that are [`Hash`] and [`Eq`] can be searched for a cycle and
+//! fast-forwarded through it.
+//!
+//! ```
+//! use aoc_common::simulation::{Runner, Simulation};
+//!
+//! #[derive(Clone, Hash, PartialEq, Eq)]
+//! struct Clock(u32);
+//!
+//! impl Simulation for Clock {
+//!     type Metric = u32;
+//!
+//!     fn step(&mut self) -> u32 {
+//!         self.0 = (self.0 + 1) % 12;
+//!         self.0
+//!     }
+//! }
+//!
+//! assert_eq!(Runner::new(Clock(0)).limit(3).collect::<Vec<_>>(), [1, 2, 3]);
+//! assert_eq!(Runner::new(Clock(0)).state_at(1_000_000_001).0, 5);
+//! ```
+
+use std::{collections::HashMap, hash::Hash};
+
+/// State that advances in discrete steps
+pub trait Simulation {
+    /// What a step reports, e.g. the number of flashes
+    type Metric;
+
+    fn step(&mut self) -> Self::Metric;
+}
+
+/// The first repeated state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the state first appeared
+    pub start: u64,
+    pub length: u64,
+}
+
+type Callback<'a, S> = Box<dyn FnMut(u64, &S, &<S as Simulation>::Metric) + 'a>;
+
+pub struct Runner<'a, S: Simulation> {
+    state: S,
+    steps: u64,
+    limit: Option<u64>,
+    callbacks: Vec<Callback<'a, S>>,
+}
+
+impl<'a, S: Simulation> Runner<'a, S> {
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            steps: 0,
+            limit: None,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Stops after `steps` steps in total
+    pub fn limit(mut self, steps: u64) -> Self {
+        self.limit = Some(steps);
+        self
+    }
+
+    /// Calls `callback` with the step number, counted from 1, the new state
+    /// and the metric after every step
+    pub fn on_step(mut self, callback: impl FnMut(u64, &S, &S::Metric) + 'a) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    /// Steps done so far
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The metric of the next step, `None` at the limit
+    pub fn step(&mut self) -> Option<S::Metric> {
+        if self.limit.is_some_and(|limit| self.steps >= limit) {
+            return None;
+        }
+        let metric = self.state.step();
+        self.steps += 1;
+        for callback in &mut self.callbacks {
+            callback(self.steps, &self.state, &metric);
+        }
+        Some(metric)
+    }
+}
+
+impl<S: Simulation + Clone + Hash + Eq> Runner<'_, S> {
+    /// Steps until a state repeats, `None` if the limit comes first. The
+    /// runner is left at the end of the first round of the cycle.
+    pub fn find_cycle(&mut self) -> Option<Cycle> {
+        let mut seen = HashMap::new();
+        loop {
+            if let Some(&start) = seen.get(&self.state) {
+                return Some(Cycle {
+                    start,
+                    length: self.steps - start,
+                });
+            }
+            seen.insert(self.state.clone(), self.steps);
+            self.step()?;
+        }
+    }
+
+    /// The state after `steps` steps in total, skipping whole rounds of a
+    /// cycle once one is found. The callbacks only see the steps taken and
+    /// the limit doesn't apply. Panics if the runner is already past `steps`.
+    pub fn state_at(mut self, steps: u64) -> S {
+        assert!(
+            steps >= self.steps,
+            "state_at({}) after {} steps",
+            steps,
+            self.steps
+        );
+        self.limit = Some(steps);
+        let first = self.steps;
+        let mut seen = HashMap::new();
+        let mut states = Vec::new();
+        loop {
+            if let Some(&start) = seen.get(&self.state) {
+                // the states repeat from `start` on
+                let offset = (steps - start) % (self.steps - start);
+                return states.swap_remove((start + offset - first) as usize);
+            }
+            seen.insert(self.state.clone(), self.steps);
+            states.push(self.state.clone());
+            if self.step().is_none() {
+                return self.state;
+            }
+        }
+    }
+}
+
+impl<S: Simulation> Iterator for Runner<'_, S> {
+    type Item = S::Metric;
+
+    fn next(&mut self) -> Option<S::Metric> {
+        self.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// `x -> x^2 + 1 mod m`, the rho of Pollard
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    struct Rho {
+        x: u64,
+        m: u64,
+    }
+
+    impl Simulation for Rho {
+        type Metric = u64;
+
+        fn step(&mut self) -> u64 {
+            self.x = (self.x * self.x + 1) % self.m;
+            self.x
+        }
+    }
+
+    fn naive(mut rho: Rho, steps: u64) -> Rho {
+        (0..steps).for_each(|_| {
+            rho.step();
+        });
+        rho
+    }
+
+    #[test]
+    fn test_limit_and_callbacks() {
+        let seen = RefCell::new(Vec::new());
+        let mut runner = Runner::new(Rho { x: 2, m: 1000 })
+            .limit(4)
+            .on_step(|step, state, metric| seen.borrow_mut().push((step, state.x, *metric)));
+        assert_eq!(runner.next(), Some(5));
+        assert_eq!(runner.by_ref().sum::<u64>(), 26 + 677 + 330);
+        assert_eq!(runner.step(), None);
+        assert_eq!(runner.steps(), 4);
+        assert_eq!(runner.state().x, 330);
+        drop(runner);
+        assert_eq!(seen.into_inner()[3], (4, 330, 330));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let rho = Rho { x: 2, m: 1000 };
+        let mut runner = Runner::new(rho.clone());
+        let cycle = runner.find_cycle().unwrap();
+        assert_eq!(
+            naive(rho.clone(), cycle.start),
+            naive(rho.clone(), cycle.start + cycle.length)
+        );
+        // the first repetition
+        assert_ne!(
+            naive(rho.clone(), cycle.start - 1),
+            naive(rho.clone(), cycle.start + cycle.length - 1)
+        );
+        assert_eq!(runner.steps(), cycle.start + cycle.length);
+
+        assert_eq!(Runner::new(rho).limit(3).find_cycle(), None);
+    }
+
+    #[test]
+    fn test_state_at() {
+        for m in [7, 1000, 1021] {
+            let rho = Rho { x: 2, m };
+            for steps in [0, 1, 5, 50, 333] {
+                assert_eq!(
+                    Runner::new(rho.clone()).state_at(steps),
+                    naive(rho.clone(), steps)
+                );
+            }
+            let far = Runner::new(rho.clone()).state_at(1 << 40);
+            let cycle = Runner::new(rho.clone()).find_cycle().unwrap();
+            let offset = ((1 << 40) - cycle.start) % cycle.length;
+            assert_eq!(far, naive(rho, cycle.start + offset));
+        }
+    }
+
+    #[test]
+    fn test_state_at_after_steps() {
+        let rho = Rho { x: 2, m: 1000 };
+        let mut runner = Runner::new(rho.clone());
+        runner.by_ref().take(7).for_each(drop);
+        assert_eq!(runner.state_at(7), naive(rho.clone(), 7));
+
+        let mut runner = Runner::new(rho.clone());
+        let cycle = runner.find_cycle().unwrap();
+        let steps = cycle.start + 10 * cycle.length + 3;
+        assert_eq!(runner.state_at(steps), naive(rho, cycle.start + 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "state_at(3) after 5 steps")]
+    fn test_state_at_past() {
+        let mut runner = Runner::new(Rho { x: 2, m: 1000 });
+        runner.by_ref().take(5).for_each(drop);
+        runner.state_at(3);
+    }
+}
@@ -0,0 +1,158 @@
+//! Snapshot tests for multi-line output like grids and rendered pages.
+//!
+//! [`assert_snapshot!`](crate::assert_snapshot) compares a value with
+//! `snapshots/<name>.snap` of the calling crate. A missing or different
+//! snapshot fails the test with a line diff, and the new output is written to
+//! `<name>.snap.new` for review. With `AOC_UPDATE_SNAPSHOTS=1` the snapshots
+//! are written instead.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const SNAPSHOT_DIR: &str = "snapshots";
+pub const UPDATE_VAR: &str = "AOC_UPDATE_SNAPSHOTS";
+
+/// Compares `actual` with the snapshot `name` of the calling crate
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::snapshot::assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")),
+            $name,
+            &$actual.to_string(),
+        )
+    };
+}
+
+pub fn snapshot_path(crate_dir: &Path, name: &str) -> PathBuf {
+    crate_dir.join(SNAPSHOT_DIR).join(format!("{}.snap", name))
+}
+
+fn is_update() -> bool {
+    env::var(UPDATE_VAR).is_ok_and(|value| value != "0")
+}
+
+/// Panics with the diff if the snapshot of the crate in `crate_dir` differs
+pub fn assert_snapshot(crate_dir: &Path, name: &str, actual: &str) {
+    if let Err(msg) = check(crate_dir, name, actual, is_update()) {
+        panic!("{}", msg);
+    }
+}
+
+/// Compares or, with `update`, writes the snapshot
+pub fn check(crate_dir: &Path, name: &str, actual: &str, update: bool) -> Result<(), String> {
+    let path = snapshot_path(crate_dir, name);
+    let new_path = path.with_extension("snap.new");
+    // a trailing newline keeps the files friendly to editors
+    let actual = format!("{}\n", actual.trim_end_matches('\n'));
+
+    let write = |path: &Path| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, &actual))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    };
+
+    if update {
+        write(&path)?;
+        let _ = fs::remove_file(&new_path);
+        return Ok(());
+    }
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {
+            let _ = fs::remove_file(&new_path);
+            return Ok(());
+        }
+        Ok(expected) => expected,
+        Err(_) => {
+            write(&new_path)?;
+            return Err(format!(
+                "snapshot {} is missing, the output was written to {}, \
+                 rename it or run the test with {}=1",
+                path.display(),
+                new_path.display(),
+                UPDATE_VAR
+            ));
+        }
+    };
+
+    write(&new_path)?;
+    Err(format!(
+        "snapshot {} doesn't match, - snapshot + actual:\n{}\n\
+         the output was written to {}, accept it with {}=1",
+        path.display(),
+        diff(&expected, &actual),
+        new_path.display(),
+        UPDATE_VAR
+    ))
+}
+
+/// Line diff of the longest common subsequence, `-` lines are only in
+/// `expected` and `+` lines only in `actual`
+pub fn diff(expected: &str, actual: &str) -> String {
+    let a = expected.lines().collect::<Vec<_>>();
+    let b = actual.lines().collect::<Vec<_>>();
+
+    // lcs[i][j] is the common length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = match a[i] == b[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push(format!("  {}", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("- {}", a[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", b[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc", "a\nc\nd"), "  a\n- b\n  c\n+ d");
+        assert_eq!(diff("", "x"), "+ x");
+        assert_eq!(diff("same", "same"), "  same");
+        assert_eq!(diff("a\nb", "a\nc"), "  a\n- b\n+ c");
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let new_path = snapshot_path(&dir, "grid").with_extension("snap.new");
+
+        let err = check(&dir, "grid", "X.\n.X", false).unwrap_err();
+        assert!(err.contains("is missing"));
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), "X.\n.X\n");
+
+        check(&dir, "grid", "X.\n.X", true).unwrap();
+        check(&dir, "grid", "X.\n.X\n", false).unwrap();
+        assert!(!new_path.exists());
+
+        let err = check(&dir, "grid", "X.\nXX", false).unwrap_err();
+        assert!(err.contains("- .X\n+ XX"));
+        assert!(new_path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+//! The interface every day implements for the runner.
+
+use std::{error, fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::Answer,
+    config::Config,
+    diff::{Rng, Variant},
+    repl::Command,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolveError {
+    /// The input doesn't have the expected shape
+    Parse(String),
+    /// The input is valid but has no answer
+    NoSolution(String),
+    /// Only parts 1 and 2 exist
+    InvalidPart(u8),
+    /// No solver for the day or part yet
+    NotImplemented,
+    /// The process solving the part didn't finish
+    Aborted(Abort),
+}
+
+/// Ways a part run in a separate process can end without a result
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Abort {
+    Timeout(Duration),
+    Panic(String),
+    OutOfMemory,
+    /// Killed by a signal or exited without reporting a result
+    Crash(String),
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Abort::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+            Abort::Panic(msg) => write!(f, "panicked: {}", msg),
+            Abort::OutOfMemory => write!(f, "out of memory"),
+            Abort::Crash(msg) => write!(f, "crashed: {}", msg),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(msg) => write!(f, "invalid input: {}", msg),
+            SolveError::NoSolution(msg) => write!(f, "no solution: {}", msg),
+            SolveError::InvalidPart(part) => write!(f, "invalid part {}", part),
+            SolveError::NotImplemented => write!(f, "not implemented"),
+            SolveError::Aborted(abort) => write!(f, "{}", abort),
+        }
+    }
+}
+
+impl error::Error for SolveError {}
+
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn part1(&self, input: &str, config: &Config) -> Result<Answer, SolveError>;
+
+    fn part2(&self, input: &str, config: &Config) -> Result<Answer, SolveError>;
+
+    fn solve(&self, part: u8, input: &str, config: &Config) -> Result<Answer, SolveError> {
+        match part {
+            1 => self.part1(input, config),
+            2 => self.part2(input, config),
+            _ => Err(SolveError::InvalidPart(part)),
+        }
+    }
+
+    /// Cheap check that `input` is plausibly this day's input, to catch inputs
+    /// of other days or years
+    fn sniff(&self, _input: &str) -> bool {
+        true
+    }
+
+    /// Alternative implementations checked against [`Solution::solve`]
+    fn variants(&self) -> Vec<Variant> {
+        Vec::new()
+    }
+
+    /// Puzzle examples for differential testing
+    fn examples(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Random valid input for differential testing, `None` if unsupported
+    fn generate(&self, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    /// The parsed input as plain data, e.g. for the Python bindings
+    fn parse(&self, _input: &str) -> Result<serde_json::Value, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    /// Commands of `aoc repl` to inspect intermediate values
+    fn commands(&self) -> Vec<Command> {
+        Vec::new()
+    }
+}
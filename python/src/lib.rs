@@ -0,0 +1,252 @@
+//! Python extension module `aoc_solvers` with the solvers of every day.
+//!
+//! ```python
+//! import aoc_solvers
+//!
+//! aoc_solvers.solve(2021, 6, 1, "3,4,3,1,2")          # 5934
+//! aoc_solvers.parse(2021, 6, "3,4,3,1,2")             # [0, 1, 1, 2, 1, 0, 0, 0, 0]
+//! aoc_solvers.day(2021, 7).part2("16,1,2,0,4,2,7,1,2,14")
+//! ```
+//!
+//! Answers are `int`, `str` or, for pictures, a `list` of `str` rows. Parsed
+//! inputs are the plain data of [`Solution::parse`] as lists, dicts, ints and
+//! strs. A [`SolveError`](aoc_common::SolveError) raises:
+//!
+//! - `ParseError` for invalid input
+//! - `NoSolutionError` for a valid input without an answer
+//! - `ValueError` for a part other than 1 and 2
+//! - `NotImplementedError` for a part or parser not written yet
+//!
+//! `ParseError` and `NoSolutionError` derive from `SolveError`, which a panic
+//! of the solver raises itself. An unknown day raises `LookupError`.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_common::{run, Abort, Answer, Config, Solution};
+use aoc_runner::solutions::SOLUTIONS;
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyLookupError, PyNotImplementedError, PyValueError},
+    prelude::*,
+    types::{PyDict, PyList},
+    IntoPyObjectExt,
+};
+use serde_json::Value;
+
+create_exception!(
+    aoc_solvers,
+    SolveError,
+    PyException,
+    "A solver failed, the base of ParseError and NoSolutionError"
+);
+create_exception!(
+    aoc_solvers,
+    ParseError,
+    SolveError,
+    "The input doesn't have the expected shape"
+);
+create_exception!(
+    aoc_solvers,
+    NoSolutionError,
+    SolveError,
+    "The input is valid but has no answer"
+);
+
+fn find(year: u16, day: u8) -> PyResult<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.year() == year && solution.day() == day)
+        .ok_or_else(|| PyLookupError::new_err(format!("no solver for {}/{:02}", year, day)))
+}
+
+/// Configuration with the puzzle parameters of `params`, values of any type
+/// are passed as their `str()`
+fn config(year: u16, day: u8, params: Option<&Bound<'_, PyDict>>) -> PyResult<Config> {
+    let mut config = Config::new(year, day);
+    for (key, value) in params.into_iter().flat_map(|params| params.iter()) {
+        config
+            .params
+            .insert(key.str()?.to_string(), value.str()?.to_string());
+    }
+    Ok(config)
+}
+
+fn to_py_err(err: aoc_common::SolveError) -> PyErr {
+    let msg = err.to_string();
+    match err {
+        aoc_common::SolveError::Parse(_) => ParseError::new_err(msg),
+        aoc_common::SolveError::NoSolution(_) => NoSolutionError::new_err(msg),
+        aoc_common::SolveError::InvalidPart(_) => PyValueError::new_err(msg),
+        aoc_common::SolveError::NotImplemented => PyNotImplementedError::new_err(msg),
+        aoc_common::SolveError::Aborted(_) => SolveError::new_err(msg),
+    }
+}
+
+/// Runs a solver without the GIL, some solvers still panic on input they can't
+/// parse
+fn call<T: Send>(
+    py: Python<'_>,
+    run: impl FnOnce() -> Result<T, aoc_common::SolveError> + Send,
+) -> PyResult<T> {
+    py.allow_threads(|| panic::catch_unwind(AssertUnwindSafe(run)))
+        .map_err(|payload| {
+            to_py_err(aoc_common::SolveError::Aborted(Abort::Panic(
+                run::panic_message(&*payload),
+            )))
+        })?
+        .map_err(to_py_err)
+}
+
+fn answer(py: Python<'_>, answer: Answer) -> PyResult<PyObject> {
+    match answer {
+        Answer::Signed(n) => n.into_py_any(py),
+        Answer::Unsigned(n) => n.into_py_any(py),
+        Answer::Big(n) => n.into_py_any(py),
+        Answer::Text(text) => text.into_py_any(py),
+        Answer::Grid(grid) => grid.rows().to_vec().into_py_any(py),
+    }
+}
+
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    match value {
+        Value::Null => Ok(py.None()),
+        Value::Bool(b) => b.into_py_any(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(n), _) => n.into_py_any(py),
+            (_, Some(n)) => n.into_py_any(py),
+            _ => n.as_f64().unwrap_or(f64::NAN).into_py_any(py),
+        },
+        Value::String(s) => s.into_py_any(py),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| to_python(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_py_any(py)
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, field) in fields {
+                dict.set_item(key, to_python(py, field)?)?;
+            }
+            dict.into_py_any(py)
+        }
+    }
+}
+
+/// The days with a solver as `(year, day)`
+#[pyfunction]
+fn days() -> Vec<(u16, u8)> {
+    SOLUTIONS
+        .iter()
+        .map(|solution| (solution.year(), solution.day()))
+        .collect()
+}
+
+/// The day's solver
+#[pyfunction]
+fn day(year: u16, day: u8) -> PyResult<Day> {
+    find(year, day).map(|solution| Day { solution })
+}
+
+/// Answer of part 1 or 2 of `input`
+#[pyfunction]
+#[pyo3(signature = (year, day, part, input, params=None))]
+fn solve(
+    py: Python<'_>,
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &str,
+    params: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Day {
+        solution: find(year, day)?,
+    }
+    .solve(py, part, input, params)
+}
+
+/// The parsed `input` as lists, dicts and numbers
+#[pyfunction]
+fn parse(py: Python<'_>, year: u16, day: u8, input: &str) -> PyResult<PyObject> {
+    Day {
+        solution: find(year, day)?,
+    }
+    .parse(py, input)
+}
+
+/// One puzzle day, e.g. `aoc_solvers.day(2021, 6)`
+#[pyclass(frozen, module = "aoc_solvers")]
+struct Day {
+    solution: &'static dyn Solution,
+}
+
+#[pymethods]
+impl Day {
+    #[getter]
+    fn year(&self) -> u16 {
+        self.solution.year()
+    }
+
+    #[getter]
+    fn day(&self) -> u8 {
+        self.solution.day()
+    }
+
+    fn parse(&self, py: Python<'_>, input: &str) -> PyResult<PyObject> {
+        let parsed = call(py, || self.solution.parse(input))?;
+        to_python(py, &parsed)
+    }
+
+    #[pyo3(signature = (input, params=None))]
+    fn part1(
+        &self,
+        py: Python<'_>,
+        input: &str,
+        params: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.solve(py, 1, input, params)
+    }
+
+    #[pyo3(signature = (input, params=None))]
+    fn part2(
+        &self,
+        py: Python<'_>,
+        input: &str,
+        params: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.solve(py, 2, input, params)
+    }
+
+    #[pyo3(signature = (part, input, params=None))]
+    fn solve(
+        &self,
+        py: Python<'_>,
+        part: u8,
+        input: &str,
+        params: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyObject> {
+        let config = config(self.year(), self.day(), params)?;
+        let result = call(py, || self.solution.solve(part, input, &config))?;
+        answer(py, result)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<aoc_solvers.Day {}/{:02}>", self.year(), self.day())
+    }
+}
+
+#[pymodule]
+fn aoc_solvers(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("SolveError", py.get_type::<SolveError>())?;
+    m.add("ParseError", py.get_type::<ParseError>())?;
+    m.add("NoSolutionError", py.get_type::<NoSolutionError>())?;
+    m.add_class::<Day>()?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(self::day, m)?)?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    Ok(())
+}
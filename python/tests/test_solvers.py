@@ -0,0 +1,168 @@
+"""Tests of the aoc_solvers extension module.
+
+The module is built with cargo and loaded from a temporary directory:
+
+    python3 -m unittest discover -s python/tests
+"""
+
+import importlib
+import os
+import shutil
+import subprocess
+import sys
+import tempfile
+import unittest
+
+CRATE_DIR = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def build_module():
+    """Builds the extension module and imports it"""
+    subprocess.run(
+        ["cargo", "build", "-q", "--manifest-path", os.path.join(CRATE_DIR, "Cargo.toml")],
+        check=True,
+    )
+    library = os.path.join(CRATE_DIR, "target", "debug", "libaoc_solvers.so")
+    module_dir = tempfile.mkdtemp()
+    shutil.copy(library, os.path.join(module_dir, "aoc_solvers.so"))
+    sys.path.insert(0, module_dir)
+    return importlib.import_module("aoc_solvers")
+
+
+aoc = build_module()
+
+FISH = "3,4,3,1,2"
+CRABS = "16,1,2,0,4,2,7,1,2,14"
+EXPENSES = "1721\n979\n366\n299\n675\n1456\n"
+CAVES = "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end\n"
+ORIGAMI = "6,10\n0,14\n9,10\n0,3\n10,4\n4,11\n6,0\n6,12\n4,1\n0,13\n10,12\n3,4\n3,0\n8,4\n1,10\n2,14\n8,10\n9,0\n\nfold along y=7\nfold along x=5\n"
+
+
+class TestSolve(unittest.TestCase):
+    def test_days(self):
+        days = aoc.days()
+        self.assertIn((2021, 6), days)
+        self.assertIn((2020, 1), days)
+        self.assertEqual(days, sorted(days))
+
+    def test_solve(self):
+        self.assertEqual(aoc.solve(2021, 6, 1, FISH), 5934)
+        self.assertEqual(aoc.solve(2021, 6, 2, FISH), 26984457539)
+        self.assertEqual(aoc.solve(2021, 12, 1, CAVES), 10)
+        self.assertIsInstance(aoc.solve(2021, 7, 1, CRABS), int)
+
+    def test_day(self):
+        day = aoc.day(2021, 7)
+        self.assertEqual((day.year, day.day), (2021, 7))
+        self.assertEqual(day.part1(CRABS), 37)
+        self.assertEqual(day.part2(CRABS), 168)
+        self.assertEqual(day.solve(2, CRABS), 168)
+        self.assertEqual(repr(day), "<aoc_solvers.Day 2021/07>")
+
+    def test_params(self):
+        self.assertEqual(aoc.solve(2020, 1, 1, EXPENSES), 514579)
+        self.assertEqual(aoc.solve(2020, 1, 1, EXPENSES, {"year": 1345}), 979 * 366)
+        self.assertEqual(aoc.day(2020, 1).part1(EXPENSES, params={"year": "1345"}), 979 * 366)
+        with self.assertRaises(aoc.ParseError):
+            aoc.solve(2020, 1, 1, EXPENSES, {"year": "soon"})
+
+    def test_grid(self):
+        grid = aoc.solve(2021, 13, 2, ORIGAMI)
+        self.assertIsInstance(grid, list)
+        self.assertTrue(all(isinstance(row, str) for row in grid))
+        self.assertEqual(grid[0], "XXXXX")
+
+
+class TestParse(unittest.TestCase):
+    def test_parse(self):
+        self.assertEqual(aoc.parse(2021, 6, FISH), [0, 1, 1, 2, 1, 0, 0, 0, 0])
+        self.assertEqual(aoc.day(2021, 7).parse(CRABS), [16, 1, 2, 0, 4, 2, 7, 1, 2, 14])
+
+    def test_nested(self):
+        origami = aoc.parse(2021, 13, ORIGAMI)
+        self.assertEqual(origami["folds"], [["y", 7], ["x", 5]])
+        self.assertIn([6, 10], origami["dots"])
+        self.assertEqual(len(origami["dots"]), 18)
+
+        edges = aoc.parse(2021, 12, CAVES)
+        self.assertIn(["start", "A"], edges)
+        self.assertNotIn(["A", "start"], edges)
+
+    def test_records(self):
+        self.assertEqual(aoc.parse(2020, 1, EXPENSES), [1721, 979, 366, 299, 675, 1456])
+        self.assertEqual(
+            aoc.parse(2020, 2, "1-3 a: abcde\n"),
+            [{"min": 1, "max": 3, "letter": "a", "password": "abcde"}],
+        )
+        self.assertEqual(
+            aoc.parse(2020, 4, "ecl:gry pid:860033327\nbyr:1937\n\niyr:2013\n"),
+            [{"ecl": "gry", "pid": "860033327", "byr": "1937"}, {"iyr": "2013"}],
+        )
+        self.assertEqual(aoc.parse(2021, 1, "199\n200\n208\n"), [199, 200, 208])
+        self.assertEqual(aoc.parse(2021, 2, "forward 5\ndown 8\n"), [["forward", 5], ["down", 8]])
+        self.assertEqual(aoc.parse(2021, 5, "0,9 -> 5,9\n"), [[[0, 9], [5, 9]]])
+
+    def test_notes(self):
+        notes = aoc.parse(
+            2021, 8, "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe\n"
+        )
+        self.assertEqual(len(notes[0]["patterns"]), 10)
+        self.assertEqual(notes[0]["output"], ["fdgacbe", "cefdb", "cefbgd", "gcbe"])
+        self.assertEqual(aoc.parse(2021, 10, "[({(<(())[]>[[{[]{<()<>>\n"), ["[({(<(())[]>[[{[]{<()<>>"])
+
+    def test_grids(self):
+        self.assertEqual(aoc.parse(2021, 9, "2199\n3987\n"), [[2, 1, 9, 9], [3, 9, 8, 7]])
+        octopuses = aoc.parse(2021, 11, "5483143223\n" * 10)
+        self.assertEqual(len(octopuses), 10)
+        self.assertEqual(octopuses[0], [5, 4, 8, 3, 1, 4, 3, 2, 2, 3])
+
+    def test_parse_errors(self):
+        with self.assertRaises(aoc.ParseError) as raised:
+            aoc.parse(2021, 2, "forward 5\nsideways 2\n")
+        self.assertEqual(
+            str(raised.exception), "invalid input: line 2, column 1: unknown command 'sideways'"
+        )
+        with self.assertRaises(aoc.ParseError) as raised:
+            aoc.parse(2021, 10, "[(x)]\n")
+        self.assertEqual(str(raised.exception), "invalid input: line 1, column 3: invalid tag 'x'")
+        with self.assertRaises(aoc.ParseError):
+            aoc.parse(2021, 8, "be cfbegad | fdgacbe cefdb cefbgd gcbe\n")
+
+
+class TestErrors(unittest.TestCase):
+    def test_parse_error(self):
+        with self.assertRaises(aoc.ParseError) as raised:
+            aoc.solve(2021, 13, 1, "6,10\n0,14\n")
+        self.assertEqual(str(raised.exception), "invalid input: no fold instructions")
+        self.assertIsInstance(raised.exception, aoc.SolveError)
+
+    def test_parse_error_position(self):
+        with self.assertRaises(aoc.ParseError) as raised:
+            aoc.solve(2021, 7, 1, "16,1,x")
+        self.assertEqual(
+            str(raised.exception), "invalid input: line 1, column 6: expected a number, found 'x'"
+        )
+
+    def test_panic(self):
+        with self.assertRaises(aoc.SolveError) as raised:
+            aoc.solve(2020, 3, 1, "")
+        self.assertTrue(str(raised.exception).startswith("panicked: "))
+        self.assertNotIsInstance(raised.exception, aoc.ParseError)
+
+    def test_no_solution(self):
+        with self.assertRaises(aoc.NoSolutionError):
+            aoc.solve(2020, 5, 2, "FBFBBFFRLR\n")
+        self.assertTrue(issubclass(aoc.NoSolutionError, aoc.SolveError))
+
+    def test_invalid_part(self):
+        with self.assertRaises(ValueError):
+            aoc.solve(2021, 6, 3, FISH)
+
+    def test_unknown_day(self):
+        with self.assertRaises(LookupError) as raised:
+            aoc.day(2019, 1)
+        self.assertEqual(str(raised.exception), "no solver for 2019/01")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,59 @@
+//! Generates the list of solvers from the `<year>/<day>` directories.
+//!
+//! Cargo only links declared dependencies, so the day crates are kept in the
+//! runner's `Cargo.toml` too. A day directory missing there is left out with
+//! a warning naming the line to add, build scripts must not touch the sources.
+
+use std::{env, fs, path::PathBuf};
+
+#[allow(dead_code)]
+#[path = "src/day_crates.rs"]
+mod day_crates;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+    let manifest = fs::read_to_string(manifest_dir.join("Cargo.toml")).unwrap();
+    let dependencies = manifest.parse::<toml::Table>().unwrap()["dependencies"]
+        .as_table()
+        .unwrap()
+        .clone();
+
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    // the year directories are scanned recursively, which covers new days.
+    // A new year isn't seen until runner/Cargo.toml changes, which linking its
+    // days takes anyway, and the runner's tests check the manifest.
+    for (_, year_dir) in day_crates::year_dirs(root) {
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+    }
+
+    let mut days = Vec::new();
+    for (year, day) in day_crates::day_crates(root) {
+        let package = day_crates::package(year, day);
+        if dependencies.contains_key(&package) {
+            days.push(package.replace('-', "_"));
+        } else {
+            println!(
+                "cargo:warning={}/{:02} isn't linked, add `{}` to the [dependencies] of \
+                 runner/Cargo.toml",
+                year,
+                day,
+                day_crates::dependency(year, day)
+            );
+        }
+    }
+
+    let solutions = days
+        .iter()
+        .map(|krate| format!("    &{}::Day,\n", krate))
+        .collect::<String>();
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(
+        out,
+        format!(
+            "pub static SOLUTIONS: &[&dyn Solution] = &[\n{}];\n",
+            solutions
+        ),
+    )
+    .unwrap();
+}
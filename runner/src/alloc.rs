@@ -0,0 +1,86 @@
+//! Counting global allocator, built with the `alloc-stats` feature.
+//!
+//! This is the only module of the runner with unsafe code: implementing
+//! [`GlobalAlloc`] is unsafe by definition, the implementation only forwards
+//! to [`System`] and counts.
+#![allow(unsafe_code)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use aoc_common::run::{MemoryStats, PartResult};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+struct Counting;
+
+fn count(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+// SAFETY: every call is forwarded unchanged to the system allocator
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn counters() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+    )
+}
+
+// writing 5 resets the peak RSS of the process on Linux, elsewhere the
+// peak is not reported at all
+fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// `VmHWM` of `/proc/self/status` in bytes
+fn peak_rss() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kib * 1024)
+}
+
+/// Runs one part and attaches the allocations made while it ran
+pub fn measure(run: impl FnOnce() -> PartResult) -> PartResult {
+    let has_peak = reset_peak_rss();
+    let (allocations, bytes) = counters();
+
+    let mut result = run();
+
+    let (allocations_after, bytes_after) = counters();
+    result.memory = Some(MemoryStats {
+        allocations: allocations_after - allocations,
+        bytes: bytes_after - bytes,
+        peak_rss: peak_rss().filter(|_| has_peak),
+    });
+    result
+}
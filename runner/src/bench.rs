@@ -0,0 +1,378 @@
+//! `aoc bench`, timing the parts and keeping a history per commit and machine.
+//!
+//! The history is a JSON-lines file which is not under version control, so it
+//! survives checking out the revisions to compare.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::{input::read_input, logging, run, Config};
+use serde::{Deserialize, Serialize};
+
+use crate::{repo_root, select, stats, take_flag};
+
+const DEFAULT_SAMPLES: usize = 10;
+const HISTORY: &str = "bench/history.jsonl";
+/// Significance level for flagging a change, over all parts of a comparison
+const ALPHA: f64 = 0.05;
+/// Smallest relative change worth flagging, below it is noise of the machine
+const MIN_CHANGE: f64 = 0.05;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub commit: String,
+    /// Samples of a working tree with changes aren't used for comparisons
+    pub dirty: bool,
+    pub machine: String,
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub samples_ns: Vec<u64>,
+}
+
+type Samples = BTreeMap<(u16, u8, u8), Vec<f64>>;
+
+pub fn bench(mut args: Vec<String>) -> Result<bool, String> {
+    let root = repo_root();
+    let history = take_flag(&mut args, "--history")?
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(HISTORY));
+    let machine = take_flag(&mut args, "--machine")?.unwrap_or_else(machine_id);
+
+    if args.first().map(String::as_str) == Some("compare") {
+        return match &args[1..] {
+            [rev_a, rev_b] => compare(&root, &history, &machine, rev_a, rev_b),
+            _ => Err("usage: aoc bench compare <rev-a> <rev-b>".to_string()),
+        };
+    }
+
+    let samples = match take_flag(&mut args, "--samples")? {
+        Some(value) => value
+            .parse::<usize>()
+            .ok()
+            .filter(|&samples| samples > 0)
+            .ok_or_else(|| format!("invalid number of samples '{}'", value))?,
+        None => DEFAULT_SAMPLES,
+    };
+    let (selected, flags) = select(args)?;
+
+    let commit = git(&root, &["rev-parse", "HEAD"])?;
+    let dirty = !git(&root, &["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    if dirty {
+        eprintln!("working tree has changes, the samples won't be used by bench compare");
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    if let Some(dir) = history.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history)
+        .map_err(|err| format!("{}: {}", history.display(), err))?;
+    let mut is_ok = true;
+
+    for solution in selected {
+        let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+            .map_err(|err| err.to_string())?;
+        logging::init(&config).map_err(|err| err.to_string())?;
+        let _day = logging::day_span(&config).entered();
+
+        let path = config.day_dir(&root).join(config.input_path());
+        let input = match read_input(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                is_ok = false;
+                continue;
+            }
+        };
+
+        for &part in &config.parts {
+            // the first run warms up caches and isn't recorded
+            let warmup = run::run_part(solution, part, &input, &config);
+            if warmup.answer.is_err() {
+                println!("{}", warmup.to_text());
+                is_ok = false;
+                continue;
+            }
+
+            let samples_ns = (0..samples)
+                .map(|_| {
+                    run::run_part(solution, part, &input, &config)
+                        .elapsed
+                        .as_nanos() as u64
+                })
+                .collect::<Vec<_>>();
+            let record = Record {
+                commit: commit.clone(),
+                dirty,
+                machine: machine.clone(),
+                timestamp,
+                year: solution.year(),
+                day: solution.day(),
+                part,
+                samples_ns,
+            };
+            println!("{}", summary(&record));
+
+            let line = serde_json::to_string(&record).map_err(|err| err.to_string())?;
+            writeln!(file, "{}", line).map_err(|err| format!("{}: {}", history.display(), err))?;
+        }
+    }
+
+    Ok(is_ok)
+}
+
+fn summary(record: &Record) -> String {
+    let samples = nanos(&record.samples_ns);
+    let deviation = match samples.len() {
+        1 => Duration::ZERO,
+        _ => duration(stats::variance(&samples).sqrt()),
+    };
+    format!(
+        "{}/{:02} part {}: {:.2?} ± {:.2?} ({} samples)",
+        record.year,
+        record.day,
+        record.part,
+        duration(stats::mean(&samples)),
+        deviation,
+        samples.len()
+    )
+}
+
+/// Prints a Markdown table of both revisions, fails on significant slowdowns
+fn compare(
+    root: &Path,
+    history: &Path,
+    machine: &str,
+    rev_a: &str,
+    rev_b: &str,
+) -> Result<bool, String> {
+    let records = load_history(history)?;
+
+    let mut revisions = Vec::new();
+    for rev in [rev_a, rev_b] {
+        let commit = git(
+            root,
+            &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)],
+        )?;
+        let samples = samples(&records, &commit, machine);
+        if samples.is_empty() {
+            return Err(format!(
+                "no samples of {} ({}) on {}, run aoc bench on it first",
+                rev,
+                short(&commit),
+                machine
+            ));
+        }
+        revisions.push((format!("{} ({})", rev, short(&commit)), samples));
+    }
+
+    let (table, slower) = table(
+        (&revisions[0].0, &revisions[0].1),
+        (&revisions[1].0, &revisions[1].1),
+    );
+    print!("{}", table);
+    Ok(slower == 0)
+}
+
+/// The table and the number of significantly slower parts. The p-values are
+/// Holm-adjusted for the number of parts compared.
+fn table(a: (&str, &Samples), b: (&str, &Samples)) -> (String, usize) {
+    let mut table = format!(
+        "| day | part | {} | {} | change | p | |\n|---|---:|---:|---:|---:|---:|---|\n",
+        a.0, b.0
+    );
+
+    // parts measured for only one revision can't be compared
+    let parts =
+        a.1.iter()
+            .filter_map(|(&key, samples_a)| {
+                let samples_b = b.1.get(&key)?;
+                let (mean_a, mean_b) = (stats::mean(samples_a), stats::mean(samples_b));
+                Some((key, mean_a, mean_b, stats::welch(samples_a, samples_b)))
+            })
+            .collect::<Vec<_>>();
+    let p = stats::holm(
+        &parts
+            .iter()
+            .filter_map(|(_, _, _, welch)| welch.map(|welch| welch.p))
+            .collect::<Vec<_>>(),
+    );
+    let mut p = p.into_iter();
+
+    let mut slower = 0;
+    for ((year, day, part), mean_a, mean_b, welch) in parts {
+        let change = mean_b / mean_a - 1.0;
+        let (p, verdict) = match welch.and_then(|_| p.next()) {
+            Some(p) if p < ALPHA && change >= MIN_CHANGE => {
+                slower += 1;
+                (format!("{:.3}", p), "**slower**")
+            }
+            Some(p) if p < ALPHA && change <= -MIN_CHANGE => (format!("{:.3}", p), "faster"),
+            Some(p) => (format!("{:.3}", p), ""),
+            None => ("-".to_string(), "too few samples"),
+        };
+        table += &format!(
+            "| {}/{:02} | {} | {:.2?} | {:.2?} | {:+.1}% | {} | {} |\n",
+            year,
+            day,
+            part,
+            duration(mean_a),
+            duration(mean_b),
+            change * 100.0,
+            p,
+            verdict
+        );
+    }
+
+    (table, slower)
+}
+
+fn load_history(path: &Path) -> Result<Vec<Record>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => return Err(format!("{}: {}", path.display(), err)),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| format!("{}:{}: {}", path.display(), idx + 1, err))
+        })
+        .collect()
+}
+
+/// All clean samples of `commit` on `machine`, runs of the same part are merged
+fn samples(records: &[Record], commit: &str, machine: &str) -> Samples {
+    let mut samples = Samples::new();
+    records
+        .iter()
+        .filter(|record| record.commit == commit && record.machine == machine && !record.dirty)
+        .for_each(|record| {
+            samples
+                .entry((record.year, record.day, record.part))
+                .or_default()
+                .extend(nanos(&record.samples_ns))
+        });
+    samples
+}
+
+fn nanos(samples: &[u64]) -> Vec<f64> {
+    samples.iter().map(|&ns| ns as f64).collect()
+}
+
+fn duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+fn machine_id() -> String {
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .map_err(|err| format!("git: {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, part: u8, samples_ns: Vec<u64>) -> Record {
+        Record {
+            commit: commit.to_string(),
+            dirty: false,
+            machine: "vm".to_string(),
+            timestamp: 0,
+            year: 2021,
+            day: 1,
+            part,
+            samples_ns,
+        }
+    }
+
+    #[test]
+    fn test_samples() {
+        let mut dirty = record("a", 1, vec![1, 2]);
+        dirty.dirty = true;
+        let records = vec![
+            record("a", 1, vec![100, 110]),
+            record("a", 1, vec![105]),
+            record("b", 1, vec![200]),
+            dirty,
+        ];
+
+        let samples = samples(&records, "a", "vm");
+        assert_eq!(samples[&(2021, 1, 1)], vec![100.0, 110.0, 105.0]);
+        assert!(super::samples(&records, "a", "other").is_empty());
+
+        let line = serde_json::to_string(&records[0]).unwrap();
+        assert_eq!(serde_json::from_str::<Record>(&line).unwrap(), records[0]);
+    }
+
+    #[test]
+    fn test_table() {
+        let records = vec![
+            record("a", 1, vec![1000, 1010, 990, 1005, 995]),
+            record("a", 2, vec![1000, 1010, 990, 1005, 995]),
+            record("b", 1, vec![1002, 1008, 992, 1001, 997]),
+            record("b", 2, vec![1500, 1510, 1490, 1505, 1495]),
+            // significant, but too small a change
+            record("a", 3, vec![1000, 1001, 999, 1000, 1000]),
+            record("b", 3, vec![1020, 1021, 1019, 1020, 1020]),
+        ];
+        let a = samples(&records, "a", "vm");
+        let b = samples(&records, "b", "vm");
+
+        let (markdown, slower) = table(("a", &a), ("b", &b));
+        assert_eq!(slower, 1);
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "| day | part | a | b | change | p | |");
+        assert!(lines[2].starts_with("| 2021/01 | 1 | 1.00µs | 1.00µs | +0.0% |"));
+        assert!(lines[2].ends_with(" |  |"));
+        assert!(lines[3].contains("| +50.0% | 0.000 | **slower** |"));
+        assert!(lines[4].contains("| +2.0% | 0.000 |  |"));
+
+        let (_, slower) = table(("b", &b), ("a", &a));
+        assert_eq!(slower, 0);
+    }
+}
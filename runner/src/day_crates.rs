@@ -0,0 +1,173 @@
+//! The day crates below the repository root and their lines in the runner's
+//! `Cargo.toml`, shared by `build.rs` and the tests of the manifest.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Number parsed from a directory name of exactly `digits` digits
+pub fn numbered(path: &Path, digits: usize) -> Option<u16> {
+    let name = path.file_name()?.to_str()?;
+    match name.len() == digits && name.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => name.parse().ok(),
+        false => None,
+    }
+}
+
+pub fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_dir())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+/// Year dirs below `root`, templates and other directories don't have
+/// numeric names
+pub fn year_dirs(root: &Path) -> Vec<(u16, PathBuf)> {
+    subdirs(root)
+        .into_iter()
+        .filter_map(|dir| Some((numbered(&dir, 4)?, dir)))
+        .collect()
+}
+
+/// `(year, day)` of every `<year>/<day>` directory with a `Cargo.toml`
+pub fn day_crates(root: &Path) -> Vec<(u16, u16)> {
+    let mut days = Vec::new();
+    for (year, year_dir) in year_dirs(root) {
+        for day_dir in subdirs(&year_dir) {
+            if let Some(day) = numbered(&day_dir, 2) {
+                if day_dir.join("Cargo.toml").is_file() {
+                    days.push((year, day));
+                }
+            }
+        }
+    }
+    days
+}
+
+pub fn package(year: u16, day: u16) -> String {
+    format!("aoc-{}-{:02}", year, day)
+}
+
+/// The line of the runner's `Cargo.toml` for a day crate
+pub fn dependency(year: u16, day: u16) -> String {
+    format!(
+        "{} = {{ path = \"../{}/{:02}\" }}",
+        package(year, day),
+        year,
+        day
+    )
+}
+
+pub fn is_day_dependency(line: &str) -> bool {
+    line.strip_prefix("aoc-20")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// `manifest` with the `lines` of the missing days added to the day
+/// dependencies, sorted
+pub fn with_days(manifest: &str, lines: &[String]) -> String {
+    let mut manifest = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+    let existing = manifest
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_day_dependency(line))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let range = match (existing.first(), existing.last()) {
+        (Some(&first), Some(&last)) => first..last + 1,
+        _ => {
+            let table = manifest
+                .iter()
+                .position(|line| line.trim() == "[dependencies]")
+                .expect("no [dependencies] in the runner's Cargo.toml");
+            table + 1..table + 1
+        }
+    };
+    let mut days = manifest[range.clone()].to_vec();
+    days.extend(lines.iter().cloned());
+    days.sort();
+    days.dedup();
+    manifest.splice(range, days);
+    manifest.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[package]
+name = \"aoc-runner\"
+
+[dependencies]
+aoc-common.workspace = true
+aoc-2020-01 = { path = \"../2020/01\" }
+aoc-2021-13 = { path = \"../2021/13\" }
+
+[target.'cfg(unix)'.dependencies]
+rlimit.workspace = true
+";
+
+    #[test]
+    fn test_is_day_dependency() {
+        assert!(is_day_dependency(&dependency(2021, 6)));
+        assert!(is_day_dependency("aoc-2020-01.workspace = true"));
+        assert!(!is_day_dependency("aoc-common.workspace = true"));
+        assert!(!is_day_dependency("aoc-runner = { path = \"../runner\" }"));
+        assert!(!is_day_dependency(" aoc-2021-06 = {}"));
+    }
+
+    #[test]
+    fn test_with_days() {
+        let manifest = with_days(MANIFEST, &[dependency(2021, 6), dependency(2022, 1)]);
+        let days = manifest
+            .lines()
+            .skip_while(|line| !line.starts_with("aoc-common"))
+            .take(5)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            days,
+            [
+                "aoc-common.workspace = true",
+                "aoc-2020-01 = { path = \"../2020/01\" }",
+                "aoc-2021-06 = { path = \"../2021/06\" }",
+                "aoc-2021-13 = { path = \"../2021/13\" }",
+                "aoc-2022-01 = { path = \"../2022/01\" }",
+            ]
+        );
+        assert!(manifest.ends_with("[target.'cfg(unix)'.dependencies]\nrlimit.workspace = true\n"));
+
+        // present days aren't repeated
+        assert_eq!(with_days(MANIFEST, &[dependency(2020, 1)]), MANIFEST);
+
+        // the first day goes at the top of the dependencies
+        let manifest = with_days("[dependencies]\nserde = \"1\"\n", &[dependency(2021, 1)]);
+        assert_eq!(
+            manifest,
+            "[dependencies]\naoc-2021-01 = { path = \"../2021/01\" }\nserde = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn test_manifest_lists_every_day() {
+        let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = fs::read_to_string(runner.join("Cargo.toml")).unwrap();
+        let days = day_crates(runner.parent().unwrap())
+            .into_iter()
+            .map(|(year, day)| dependency(year, day))
+            .collect::<Vec<_>>();
+        assert!(!days.is_empty());
+        assert_eq!(
+            with_days(&manifest, &days),
+            manifest,
+            "day crates missing from runner/Cargo.toml"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! `aoc diff`, differential testing of the registered variants.
+
+use aoc_common::{diff, logging, Config};
+
+use crate::{select, take_flag};
+
+const DEFAULT_INPUTS: usize = 1000;
+
+pub fn diff(mut args: Vec<String>) -> Result<bool, String> {
+    let inputs = match take_flag(&mut args, "--inputs")? {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| format!("invalid number of inputs '{}'", value))?,
+        None => DEFAULT_INPUTS,
+    };
+    let seed = match take_flag(&mut args, "--seed")? {
+        Some(value) => value
+            .parse::<u64>()
+            .map_err(|_| format!("invalid seed '{}'", value))?,
+        None => 0,
+    };
+    let (selected, flags) = select(args)?;
+
+    for solution in selected {
+        let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+            .map_err(|err| err.to_string())?;
+        logging::init(&config).map_err(|err| err.to_string())?;
+
+        if solution.variants().is_empty() {
+            continue;
+        }
+
+        match diff::check(solution, &config, inputs, seed) {
+            Ok(summary) => println!(
+                "{}/{:02}: {} comparisons on {} inputs agree",
+                solution.year(),
+                solution.day(),
+                summary.comparisons,
+                summary.inputs
+            ),
+            Err(disagreement) => {
+                println!("{}", disagreement);
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
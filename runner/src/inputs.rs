@@ -0,0 +1,230 @@
+//! `aoc inputs`, encrypting the puzzle inputs for the repository.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{
+    crypto::{self, Costs, Key},
+    Config,
+};
+
+use crate::{repo_root, select, take_switch};
+
+const USAGE: &str = "usage: aoc inputs encrypt|decrypt [<year> [<day>]] [--force]";
+
+pub fn inputs(mut args: Vec<String>) -> Result<bool, String> {
+    let force = take_switch(&mut args, "--force");
+    if args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let command = args.remove(0);
+    let (selected, flags) = select(args)?;
+    let root = repo_root();
+    let passphrase = crypto::passphrase().map_err(|err| err.to_string())?;
+    let mut is_ok = true;
+
+    // one key for all inputs, deriving it is slow on purpose
+    let key = match command.as_str() {
+        "encrypt" => Some(Key::new(&passphrase, Costs::default()).map_err(|err| err.to_string())?),
+        "decrypt" => None,
+        _ => return Err(USAGE.to_string()),
+    };
+
+    for solution in selected {
+        let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+            .map_err(|err| err.to_string())?;
+        // the default input and the named inputs of all users
+        let day_dir = config.day_dir(&root);
+        let users = config.users(&day_dir);
+        let configs = std::iter::once(Config {
+            user: None,
+            ..config.clone()
+        })
+        .chain(users.iter().map(|user| config.for_user(user)));
+
+        for config in configs {
+            let Some((ok, status)) = process(&config, &root, key.as_ref(), &passphrase, force)?
+            else {
+                continue;
+            };
+            let label = match &config.user {
+                Some(user) => format!("{} [{}]", config.day_key(), user),
+                None => config.day_key(),
+            };
+            println!("{}: {}", label, status);
+            is_ok &= ok;
+        }
+    }
+
+    Ok(is_ok)
+}
+
+/// Encrypts or, without a `key`, decrypts the input of `config`. Returns
+/// whether it went fine and what was done, `None` if there is no input.
+fn process(
+    config: &Config,
+    root: &Path,
+    key: Option<&Key>,
+    passphrase: &str,
+    force: bool,
+) -> Result<Option<(bool, String)>, String> {
+    let mut is_ok = true;
+    let plain = config.day_dir(root).join(config.input_path());
+    let encrypted = crypto::encrypted_path(&plain);
+    // e.g. 2021/01/data/input.enc
+    let display = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .collect::<PathBuf>()
+            .display()
+            .to_string()
+    };
+
+    // existing files are compared first, to keep them when nothing changed
+    let existing = fs::read(&encrypted)
+        .ok()
+        .map(|data| crypto::decrypt(&data, passphrase));
+
+    let status = match key {
+        Some(key) => {
+            let Ok(input) = fs::read_to_string(&plain) else {
+                return Ok(None);
+            };
+            match existing {
+                Some(Ok(existing)) if existing == input => "unchanged".to_string(),
+                Some(Err(err)) if !force => {
+                    is_ok = false;
+                    format!("{}, --force replaces it", err)
+                }
+                _ => {
+                    fs::write(&encrypted, key.encrypt(&input))
+                        .map_err(|err| format!("{}: {}", encrypted.display(), err))?;
+                    format!("encrypted to {}", display(&encrypted))
+                }
+            }
+        }
+        None => match existing {
+            None => return Ok(None),
+            Some(Err(err)) => {
+                is_ok = false;
+                err.to_string()
+            }
+            Some(Ok(input)) => match fs::read_to_string(&plain) {
+                Ok(plain_input) if plain_input == input => "unchanged".to_string(),
+                Ok(_) if !force => {
+                    is_ok = false;
+                    "the plain input differs, --force overwrites it".to_string()
+                }
+                _ => {
+                    fs::write(&plain, input)
+                        .map_err(|err| format!("{}: {}", plain.display(), err))?;
+                    format!("decrypted to {}", display(&plain))
+                }
+            },
+        },
+    };
+    Ok(Some((is_ok, status)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    // the minimum, tests shouldn't spend time on key derivation
+    const CHEAP: Costs = Costs {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn temp_root(name: &str) -> (PathBuf, PathBuf) {
+        let root = env::temp_dir().join(format!("aoc-inputs-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let data = root.join("2021").join("06").join("data");
+        fs::create_dir_all(&data).unwrap();
+        (root, data)
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let (root, data) = temp_root("roundtrip");
+        let config = Config::new(2021, 6);
+        let key = Key::new("correct horse", CHEAP).unwrap();
+        fs::write(data.join("input"), "3,4,3,1,2\n").unwrap();
+
+        let (ok, status) = process(&config, &root, Some(&key), "correct horse", false)
+            .unwrap()
+            .unwrap();
+        assert!(ok);
+        assert_eq!(status, "encrypted to 2021/06/data/input.enc");
+        let encrypted = fs::read(data.join("input.enc")).unwrap();
+        assert!(!encrypted.windows(5).any(|window| window == b"3,4,3"));
+
+        let (_, status) = process(&config, &root, Some(&key), "correct horse", false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(status, "unchanged");
+
+        fs::remove_file(data.join("input")).unwrap();
+        let (ok, status) = process(&config, &root, None, "correct horse", false)
+            .unwrap()
+            .unwrap();
+        assert!(ok);
+        assert_eq!(status, "decrypted to 2021/06/data/input");
+        assert_eq!(
+            fs::read_to_string(data.join("input")).unwrap(),
+            "3,4,3,1,2\n"
+        );
+
+        // nothing to do for a day without inputs
+        let other = Config::new(2021, 7);
+        assert_eq!(
+            process(&other, &root, Some(&key), "correct horse", false).unwrap(),
+            None
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let (root, data) = temp_root("wrong");
+        let config = Config::new(2021, 6);
+        let key = Key::new("correct horse", CHEAP).unwrap();
+        fs::write(data.join("input"), "3,4,3,1,2\n").unwrap();
+        process(&config, &root, Some(&key), "correct horse", false).unwrap();
+
+        let (ok, status) = process(&config, &root, None, "battery staple", false)
+            .unwrap()
+            .unwrap();
+        assert!(!ok);
+        assert_eq!(
+            status,
+            "wrong passphrase, or the encrypted input was modified"
+        );
+        assert_eq!(
+            fs::read_to_string(data.join("input")).unwrap(),
+            "3,4,3,1,2\n"
+        );
+
+        // an input encrypted with another passphrase is only replaced by force
+        let other = Key::new("battery staple", CHEAP).unwrap();
+        let (ok, status) = process(&config, &root, Some(&other), "battery staple", false)
+            .unwrap()
+            .unwrap();
+        assert!(!ok);
+        assert_eq!(
+            status,
+            "wrong passphrase, or the encrypted input was modified, --force replaces it"
+        );
+        let (ok, _) = process(&config, &root, Some(&other), "battery staple", true)
+            .unwrap()
+            .unwrap();
+        assert!(ok);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+//! Running parts in child processes with a timeout and a memory limit.
+//!
+//! The child is the runner itself, `aoc run <year> <day> --part <n> --child`,
+//! which prints its [`PartResult`] as one JSON line. A timeout, a panic or the
+//! memory limit end the child and are reported as [`Abort`].
+
+use std::{
+    env,
+    io::{self, Read},
+    path::PathBuf,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{run::PartResult, Abort, Solution};
+
+use crate::take_flag;
+
+/// Hidden flag of the child processes
+pub const CHILD: &str = "--child";
+
+// exit code of a panicking Rust process
+const PANIC_EXIT_CODE: i32 = 101;
+const SIGKILL: i32 = 9;
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+pub struct Isolation {
+    exe: PathBuf,
+    /// Configuration flags passed on to every child
+    flags: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub memory_limit: Option<u64>,
+}
+
+impl Isolation {
+    pub fn new(
+        flags: &[String],
+        timeout: Option<Duration>,
+        memory_limit: Option<u64>,
+    ) -> Result<Self, String> {
+        let exe = env::current_exe().map_err(|err| format!("runner executable: {}", err))?;
+        // the child gets exactly one part
+        let mut flags = flags.to_vec();
+        while take_flag(&mut flags, "--part")?.is_some() {}
+
+        Ok(Self {
+            exe,
+            flags,
+            timeout,
+            memory_limit,
+        })
+    }
+
+    /// Runs `part` on the named input of `user` or the default input
+    pub fn run_part(&self, solution: &dyn Solution, part: u8, user: Option<&str>) -> PartResult {
+        let (year, day) = (solution.year(), solution.day());
+        let aborted = |abort, elapsed| PartResult {
+            user: user.map(str::to_string),
+            ..PartResult::aborted(year, day, part, abort, elapsed)
+        };
+
+        let mut command = Command::new(&self.exe);
+        command
+            .args(["run", &year.to_string(), &day.to_string(), CHILD])
+            .args(["--part", &part.to_string()])
+            .args(&self.flags)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(memory_limit) = self.memory_limit {
+            command.args(["--memory-limit", &memory_limit.to_string()]);
+        }
+        if let Some(user) = user {
+            command.args(["--user", user]);
+        }
+
+        let start = Instant::now();
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(err) => return aborted(Abort::Crash(err.to_string()), start.elapsed()),
+        };
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+
+        let status = wait(&mut child, self.timeout);
+        let elapsed = start.elapsed();
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        let status = match status {
+            Ok(Some(status)) => status,
+            Ok(None) => return aborted(Abort::Timeout(elapsed), elapsed),
+            Err(err) => return aborted(Abort::Crash(err.to_string()), elapsed),
+        };
+
+        if status.success() {
+            // warnings of the child, e.g. about the input
+            eprint!("{}", stderr);
+            if let Some(result) = stdout
+                .lines()
+                .last()
+                .and_then(|line| serde_json::from_str::<PartResult>(line).ok())
+            {
+                return result;
+            }
+        }
+        aborted(
+            classify(status, &stderr, self.memory_limit.is_some()),
+            elapsed,
+        )
+    }
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// `None` if the child was killed after `timeout`
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn classify(status: ExitStatus, stderr: &str, has_memory_limit: bool) -> Abort {
+    // under the memory limit a failed allocation aborts the process and the
+    // kernel's OOM killer sends SIGKILL, timeouts are handled before
+    if has_memory_limit
+        && (stderr.contains("memory allocation of") || signal(&status) == Some(SIGKILL))
+    {
+        return Abort::OutOfMemory;
+    }
+    if status.code() == Some(PANIC_EXIT_CODE) {
+        return Abort::Panic(panic_message(stderr));
+    }
+    match (status.code(), signal(&status)) {
+        (_, Some(signal)) => Abort::Crash(format!("signal {}", signal)),
+        (Some(code), _) => Abort::Crash(format!("exit code {}", code)),
+        _ => Abort::Crash(status.to_string()),
+    }
+}
+
+#[cfg(unix)]
+fn signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// The message following `thread '...' panicked at <location>:`
+fn panic_message(stderr: &str) -> String {
+    let mut lines = stderr
+        .lines()
+        .skip_while(|line| !line.contains("panicked at"));
+    let location = lines.next().unwrap_or_default();
+    let message = lines
+        .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace:"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    match message.is_empty() {
+        true => location.trim().to_string(),
+        false => message,
+    }
+}
+
+/// Limits the address space of the current process, used by the children
+#[cfg(unix)]
+pub fn limit_memory(bytes: u64) -> Result<(), String> {
+    rlimit::Resource::AS
+        .set(bytes, bytes)
+        .map_err(|err| format!("can't set the memory limit: {}", err))
+}
+
+#[cfg(not(unix))]
+pub fn limit_memory(_bytes: u64) -> Result<(), String> {
+    Err("memory limits are only supported on Unix".to_string())
+}
+
+/// Seconds by default, or with an `ms`, `s`, `m` or `h` suffix
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{}'", s);
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let value = s[..split].trim().parse::<f64>().map_err(|_| invalid())?;
+    let seconds = match &s[split..] {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(invalid()),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+}
+
+/// Bytes by default, or with a binary `K`, `M` or `G` suffix, e.g. `512M` or `1GiB`
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid size '{}'", s);
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let value = s[..split].trim().parse::<u64>().map_err(|_| invalid())?;
+    let shift = match s[split..].trim_end_matches("iB").trim_end_matches('B') {
+        "" => 0,
+        "K" | "k" => 10,
+        "M" => 20,
+        "G" => 30,
+        _ => return Err(invalid()),
+    };
+    value.checked_mul(1 << shift).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("-1s").is_err());
+
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("512M"), Ok(512 << 20));
+        assert_eq!(parse_size("1GiB"), Ok(1 << 30));
+        assert_eq!(parse_size("2KB"), Ok(2048));
+        assert!(parse_size("1T").is_err());
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let stderr = "thread 'main' panicked at 2021/12/src/lib.rs:10:5:\n\
+                      index out of bounds\n\
+                      stack backtrace:\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable";
+        assert_eq!(panic_message(stderr), "index out of bounds");
+        assert_eq!(
+            panic_message("thread 'main' panicked at src/lib.rs:1:1:"),
+            "thread 'main' panicked at src/lib.rs:1:1:"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_classify() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let killed = ExitStatus::from_raw(SIGKILL);
+        let aborted = ExitStatus::from_raw(6);
+        let alloc = "memory allocation of 1048576 bytes failed\n";
+        assert_eq!(classify(aborted, alloc, true), Abort::OutOfMemory);
+        assert_eq!(
+            classify(aborted, alloc, false),
+            Abort::Crash("signal 6".to_string())
+        );
+        // OOM kills only count with a memory limit
+        assert_eq!(classify(killed, "", true), Abort::OutOfMemory);
+        assert_eq!(
+            classify(killed, "", false),
+            Abort::Crash("signal 9".to_string())
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(PANIC_EXIT_CODE << 8), "", false),
+            Abort::Panic(String::new())
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! The solvers known to the runner, for the `aoc` binary and for front ends
+//! like the Python bindings.
+
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+#[cfg(test)]
+mod day_crates;
+pub mod solutions;
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::config;
+
+/// Directory of the repository `aoc.toml`, the day inputs are below it
+pub fn repo_root() -> PathBuf {
+    let cwd = env::current_dir().unwrap_or_default();
+    config::find_repo_file(&cwd)
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or(cwd)
+}
@@ -0,0 +1,244 @@
+#![deny(clippy::all)]
+#![deny(unsafe_code)]
+#![deny(warnings)]
+
+#[cfg(feature = "alloc-stats")]
+mod alloc;
+mod bench;
+mod diff;
+mod inputs;
+mod isolate;
+mod repl;
+mod report;
+mod run;
+mod serve;
+mod stats;
+mod watch;
+
+use std::{env, process};
+
+use aoc_common::Solution;
+pub(crate) use aoc_runner::repo_root;
+use aoc_runner::solutions::{self, NotImplemented};
+
+const USAGE: &str = "usage: aoc <command> [<year> [<day>]] [options]
+
+commands:
+  run    solve the stored inputs and compare with the stored answers,
+         --record saves the answers and the input fingerprint,
+         --isolate runs every part in a child process which is killed
+         after --timeout <duration>, e.g. 30s; --memory-limit <size>,
+         e.g. 512M, limits the memory of the child or of the runner;
+         --all-inputs runs the default input and every data/inputs/<user>
+  diff   compare alternative implementations, --inputs <n> generated
+         inputs per day starting at --seed <n>
+  bench  time every part --samples <n> times and append the samples to
+         the history, bench compare <rev-a> <rev-b> prints a Markdown
+         table of the changes and fails on significant slowdowns of
+         5% or more
+  inputs encrypt the inputs to data/input.enc with a key derived from
+         $AOC_PASSPHRASE or ~/.config/aoc/passphrase, or decrypt them;
+         inputs missing in plain text are decrypted when they are read
+  report write a static HTML site with stars, answers and timings to
+         --out <dir> (default site/), --redact hides the answers
+  repl   explore the parsed input of <year> <day> with the day's commands,
+         help lists them
+  serve  JSON HTTP API on --port <port> (default 8025) of --host <addr>
+         (default 127.0.0.1): GET /days lists the days, POST an input to
+         /solve/<year>/<day>/<part> for the answer and timing; requests
+         slower than --read-timeout <duration> (default 10s) get a 408
+  watch  rebuild <year> <day> when its src/ or data/ changes, run the
+         example tests and solve the input
+
+solvers built as shared libraries with aoc_common::export_plugin! are loaded
+from plugins/ or $AOC_PLUGIN_DIR, a plugin replaces the built-in solver of its
+day
+
+options are the same as for the day binaries, e.g. --part 1, --format json or
+--user <name> for the named input data/inputs/<name>
+
+built with --features alloc-stats, run also reports the allocations and the
+peak resident memory of every part";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+
+    let result = match command.as_deref() {
+        Some("run") => run::run(args.collect()),
+        Some("diff") => diff::diff(args.collect()),
+        Some("bench") => bench::bench(args.collect()),
+        Some("report") => report::report(args.collect()),
+        Some("repl") => repl::repl(args.collect()),
+        Some("serve") => serve::serve(args.collect()),
+        Some("inputs") => inputs::inputs(args.collect()),
+        Some("watch") => watch::watch(args.collect()),
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(msg) => {
+            eprintln!("{}", msg);
+            process::exit(2);
+        }
+    }
+}
+
+/// Days matching the leading `<year> [<day>]` arguments and the remaining flags
+pub(crate) fn select(
+    args: Vec<String>,
+) -> Result<(Vec<&'static dyn Solution>, Vec<String>), String> {
+    let mut args = args.into_iter().peekable();
+
+    let year = match args.peek().map(|arg| arg.parse::<u16>()) {
+        Some(Ok(year)) => {
+            args.next();
+            Some(year)
+        }
+        _ => None,
+    };
+    let day = match (year, args.peek().map(|arg| arg.parse::<u8>())) {
+        (Some(_), Some(Ok(day))) => {
+            args.next();
+            Some(day)
+        }
+        _ => None,
+    };
+
+    let selected = solutions::all()
+        .iter()
+        .copied()
+        .filter(|solution| year.is_none_or(|year| solution.year() == year))
+        .filter(|solution| day.is_none_or(|day| solution.day() == day))
+        .collect::<Vec<_>>();
+
+    if selected.is_empty() {
+        return match (year, day) {
+            // a missing day is a result, its parts report not being implemented
+            (Some(year), Some(day @ 1..=25)) => {
+                let missing: &'static NotImplemented =
+                    Box::leak(Box::new(NotImplemented { year, day }));
+                Ok((vec![missing], args.collect()))
+            }
+            (Some(year), Some(day)) => Err(format!("{}/{:02} is not a puzzle day", year, day)),
+            (Some(year), None) => Err(format!("no days implemented for {}", year)),
+            _ => Err("no days implemented".to_string()),
+        };
+    }
+
+    Ok((selected, args.collect()))
+}
+
+/// Removes the flag `name` from `args`, true if it was present
+pub(crate) fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+/// Removes `--name <value>` or `--name=<value>` from `args`
+pub(crate) fn take_flag(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let Some(idx) = args
+        .iter()
+        .position(|arg| arg == name || arg.starts_with(&format!("{}=", name)))
+    else {
+        return Ok(None);
+    };
+
+    let arg = args.remove(idx);
+    match arg.split_once('=') {
+        Some((_, value)) => Ok(Some(value.to_string())),
+        None if idx < args.len() => Ok(Some(args.remove(idx))),
+        None => Err(format!("missing value for {}", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::stored_input, Config};
+
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let (selected, flags) = select(args("2021 12 --part 1")).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].day(), 12);
+        assert_eq!(flags, args("--part 1"));
+
+        let (selected, _) = select(args("2020")).unwrap();
+        assert!(selected.iter().all(|solution| solution.year() == 2020));
+
+        let (selected, _) = select(args("2021 25")).unwrap();
+        let config = Config::new(2021, 25);
+        assert_eq!(
+            selected[0].part1("", &config),
+            Err(aoc_common::SolveError::NotImplemented)
+        );
+        assert!(select(args("2021 26")).is_err());
+        assert!(select(args("2019")).is_err());
+    }
+
+    #[test]
+    fn test_take_flag() {
+        let mut flags = args("--seed 3 --part 1 --inputs=10");
+        assert_eq!(
+            take_flag(&mut flags, "--inputs").unwrap(),
+            Some("10".to_string())
+        );
+        assert_eq!(
+            take_flag(&mut flags, "--seed").unwrap(),
+            Some("3".to_string())
+        );
+        assert_eq!(take_flag(&mut flags, "--seed").unwrap(), None);
+        assert_eq!(flags, args("--part 1"));
+
+        assert!(take_flag(&mut args("--seed"), "--seed").is_err());
+
+        let mut flags = args("--record --part 1");
+        assert!(take_switch(&mut flags, "--record"));
+        assert!(!take_switch(&mut flags, "--record"));
+        assert_eq!(flags, args("--part 1"));
+    }
+
+    #[test]
+    fn test_sniff_stored_inputs() {
+        let root = repo_root();
+        // days without a readable input are skipped
+        let input_of = |solution: &dyn Solution| {
+            let config = Config::new(solution.year(), solution.day());
+            stored_input(&config.day_dir(&root).join(config.input_path()))
+        };
+
+        for solution in solutions::SOLUTIONS {
+            if let Some(input) = input_of(*solution) {
+                assert!(
+                    solution.sniff(&input),
+                    "{}/{:02}",
+                    solution.year(),
+                    solution.day()
+                );
+            }
+        }
+
+        let (bingo, vents) = select(vec!["2021".to_string()])
+            .map(|(selected, _)| (selected[3], selected[4]))
+            .unwrap();
+        if let Some(input) = input_of(vents) {
+            assert!(!bingo.sniff(&input));
+        }
+        if let Some(input) = input_of(bingo) {
+            assert!(!vents.sniff(&input));
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! `aoc repl`, a shell for poking at the parsed input of one day.
+//!
+//! Besides the built-in commands every day can offer its own
+//! [`Command`](aoc_common::repl::Command)s, e.g. `calc_cost2 5` in 2021/07.
+//! Every command is timed.
+
+use std::{
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Component, Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{input::read_input, run, Config, Solution};
+
+use crate::{repo_root, select};
+
+const USAGE: &str = "usage: aoc repl <year> <day> [options]";
+
+// lines of output shown by default, long debug dumps are cut
+const DEFAULT_LIMIT: usize = 40;
+
+const HELP: &str = "\
+built-in commands:
+  load [<path> | example <n> | user <name>]
+                    load an input, the configured one without arguments
+  input             size and first lines of the loaded input
+  part <n>          solve part n of the loaded input
+  time <n> <cmd>    run a command n times, with the mean and fastest time
+  limit <n>         lines of output shown, 0 shows everything
+  help              this help
+  quit              leave, as does end of input";
+
+pub fn repl(args: Vec<String>) -> Result<bool, String> {
+    let is_day = args.len() >= 2 && args[..2].iter().all(|arg| arg.parse::<u16>().is_ok());
+    let (selected, flags) = select(args)?;
+    let solution = match selected[..] {
+        [solution] if is_day => solution,
+        _ => return Err(USAGE.to_string()),
+    };
+    let config =
+        Config::load_from(solution.year(), solution.day(), flags).map_err(|err| err.to_string())?;
+
+    let mut repl = Repl::new(solution, config, repo_root());
+    match repl.eval("load") {
+        Ok(reply) => println!("{}", reply.text),
+        Err(err) => eprintln!("{}", err),
+    }
+    println!("help lists the commands");
+
+    let prompt = format!("{}> ", repl.config.day_key());
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("{}", prompt);
+        io::stdout().flush().map_err(|err| err.to_string())?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(true);
+        };
+        let line = line.map_err(|err| err.to_string())?;
+
+        match repl.eval(&line) {
+            Ok(reply) if reply.quit => return Ok(true),
+            Ok(reply) => {
+                if !reply.text.is_empty() {
+                    println!("{}", repl.truncate(&reply.text));
+                }
+                if let Some(elapsed) = reply.elapsed {
+                    println!("({})", elapsed);
+                }
+            }
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Reply {
+    text: String,
+    /// Timing of the command, if it ran one
+    elapsed: Option<String>,
+    quit: bool,
+}
+
+impl Reply {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+}
+
+struct Repl<'a> {
+    solution: &'a dyn Solution,
+    config: Config,
+    root: PathBuf,
+    input: String,
+    limit: usize,
+}
+
+impl<'a> Repl<'a> {
+    fn new(solution: &'a dyn Solution, config: Config, root: PathBuf) -> Self {
+        Self {
+            solution,
+            config,
+            root,
+            input: String::new(),
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    fn eval(&mut self, line: &str) -> Result<Reply, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&name, args)) = words.split_first() else {
+            return Ok(Reply::default());
+        };
+
+        match name {
+            "quit" | "exit" => Ok(Reply {
+                quit: true,
+                ..Reply::default()
+            }),
+            "help" => Ok(Reply::text(self.help())),
+            "load" => self.load(args),
+            "input" => Ok(Reply::text(self.describe_input())),
+            "limit" => {
+                self.limit = parse(args.first(), "n")?;
+                Ok(Reply::default())
+            }
+            "time" => {
+                let runs = parse::<u32>(args.first(), "n")?.max(1);
+                let (&name, args) = args
+                    .get(1..)
+                    .and_then(|rest| rest.split_first())
+                    .ok_or("missing command to time")?;
+                let mut times = Vec::new();
+                let mut text = String::new();
+                for _ in 0..runs {
+                    let start = Instant::now();
+                    text = self.run(name, args)?;
+                    times.push(start.elapsed());
+                }
+                let mean = times.iter().sum::<Duration>() / runs;
+                let fastest = times.iter().min().copied().unwrap_or_default();
+                Ok(Reply {
+                    text,
+                    elapsed: Some(format!(
+                        "{} runs, mean {:?}, fastest {:?}",
+                        runs, mean, fastest
+                    )),
+                    quit: false,
+                })
+            }
+            name => {
+                let start = Instant::now();
+                let text = self.run(name, args)?;
+                Ok(Reply {
+                    text,
+                    elapsed: Some(format!("{:?}", start.elapsed())),
+                    quit: false,
+                })
+            }
+        }
+    }
+
+    /// Solves a part or runs one of the day's commands on the loaded input
+    fn run(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        if name == "part" {
+            let part = parse(args.first(), "n")?;
+            return catch(|| {
+                self.solution
+                    .solve(part, &self.input, &self.config)
+                    .map(|answer| answer.to_string())
+                    .map_err(|err| err.to_string())
+            });
+        }
+
+        let command = self
+            .solution
+            .commands()
+            .into_iter()
+            .find(|command| command.name == name)
+            .ok_or_else(|| format!("unknown command '{}', help lists the commands", name))?;
+        catch(|| (command.run)(&self.input, args, &self.config))
+    }
+
+    fn load(&mut self, args: &[&str]) -> Result<Reply, String> {
+        let (input, source) = match args {
+            [] => self.read(&self.config.input_path())?,
+            ["example", n] => {
+                let examples = self.solution.examples();
+                let idx = n
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|idx| (1..=examples.len()).contains(idx))
+                    .ok_or_else(|| format!("there are {} examples", examples.len()))?;
+                (examples[idx - 1].to_string(), format!("example {}", idx))
+            }
+            ["user", user] => self.read(&self.config.for_user(user).input_path())?,
+            [path] => {
+                let input =
+                    read_input(Path::new(path)).map_err(|err| format!("{}: {}", path, err))?;
+                (input, path.to_string())
+            }
+            _ => return Err("usage: load [<path> | example <n> | user <name>]".to_string()),
+        };
+
+        self.input = input;
+        Ok(Reply::text(format!(
+            "loaded {}, {}",
+            source,
+            self.describe_size()
+        )))
+    }
+
+    /// Input of the day directory, with its path relative to it
+    fn read(&self, path: &Path) -> Result<(String, String), String> {
+        let full_path = self.config.day_dir(&self.root).join(path);
+        let input =
+            read_input(&full_path).map_err(|err| format!("{}: {}", full_path.display(), err))?;
+        let source = path
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .collect::<PathBuf>()
+            .display()
+            .to_string();
+        Ok((input, source))
+    }
+
+    fn describe_size(&self) -> String {
+        format!(
+            "{} lines, {} bytes",
+            self.input.lines().count(),
+            self.input.len()
+        )
+    }
+
+    fn describe_input(&self) -> String {
+        let head = self.input.lines().take(5).collect::<Vec<_>>().join("\n");
+        format!("{}\n{}", self.describe_size(), head)
+    }
+
+    fn help(&self) -> String {
+        let mut help = format!("{}\n", HELP);
+        let commands = self.solution.commands();
+        if commands.is_empty() {
+            help.push_str("\nno commands for this day yet");
+        } else {
+            help.push_str(&format!("\ncommands of {}:", self.config.day_key()));
+            for command in commands {
+                let usage = format!("{} {}", command.name, command.args);
+                help.push_str(&format!("\n  {:<18}{}", usage.trim_end(), command.help));
+            }
+        }
+        help
+    }
+
+    /// The first lines of `text` within the limit
+    fn truncate(&self, text: &str) -> String {
+        let count = text.lines().count();
+        if self.limit == 0 || count <= self.limit {
+            return text.to_string();
+        }
+        let mut lines = text.lines().take(self.limit).collect::<Vec<_>>();
+        let more = format!(
+            "... {} more lines, limit 0 shows everything",
+            count - self.limit
+        );
+        lines.push(&more);
+        lines.join("\n")
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: Option<&&str>, name: &str) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing argument <{}>", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid <{}> '{}'", name, value))
+}
+
+/// A panicking solver or command doesn't end the session
+fn catch(run: impl FnOnce() -> Result<String, String>) -> Result<String, String> {
+    panic::catch_unwind(AssertUnwindSafe(run))
+        .unwrap_or_else(|payload| Err(format!("panicked: {}", run::panic_message(&*payload))))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::stored_input;
+    use aoc_runner::solutions::SOLUTIONS;
+
+    use super::*;
+
+    fn day(year: u16, day: u8) -> &'static dyn Solution {
+        *SOLUTIONS
+            .iter()
+            .find(|solution| solution.year() == year && solution.day() == day)
+            .unwrap()
+    }
+
+    fn text(repl: &mut Repl, line: &str) -> String {
+        repl.eval(line).unwrap().text
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut repl = Repl::new(day(2021, 7), Config::new(2021, 7), repo_root());
+        repl.input = "16,1,2,0,4,2,7,1,2,14".to_string();
+
+        assert_eq!(text(&mut repl, "part 1"), "37");
+        assert_eq!(text(&mut repl, "calc_cost2 5"), "168");
+        assert_eq!(text(&mut repl, "calc_cost  2"), "37");
+        assert!(repl.eval("calc_cost2 5").unwrap().elapsed.is_some());
+        assert!(repl
+            .eval("time 3 calc_cost 2")
+            .unwrap()
+            .elapsed
+            .unwrap()
+            .starts_with("3 runs, mean "));
+
+        assert_eq!(
+            repl.eval("calc_cost2"),
+            Err("missing argument <pos>".to_string())
+        );
+        assert!(repl
+            .eval("unknown")
+            .unwrap_err()
+            .contains("unknown command"));
+        assert!(text(&mut repl, "help").contains("calc_cost2 <pos>"));
+        assert!(repl.eval("quit").unwrap().quit);
+        assert_eq!(repl.eval("  ").unwrap(), Reply::default());
+    }
+
+    #[test]
+    fn test_load() {
+        let mut repl = Repl::new(day(2021, 12), Config::new(2021, 12), repo_root());
+        assert_eq!(
+            text(&mut repl, "load example 1"),
+            "loaded example 1, 8 lines, 81 bytes"
+        );
+        assert!(text(&mut repl, "cave_edges").starts_with("start -> A\nstart -> b\nA -> c"));
+        assert_eq!(text(&mut repl, "paths 1").lines().count(), 10);
+        assert_eq!(text(&mut repl, "paths 2").lines().count(), 36);
+        assert!(repl.eval("load example 9").is_err());
+
+        let config = Config::new(2021, 12);
+        if stored_input(&config.day_dir(&repo_root()).join(config.input_path())).is_some() {
+            assert!(text(&mut repl, "load").starts_with("loaded data/input, "));
+        }
+        assert!(repl.eval("load user nobody").is_err());
+    }
+
+    #[test]
+    fn test_catch_panics() {
+        let mut repl = Repl::new(day(2020, 3), Config::new(2020, 3), repo_root());
+        repl.input = String::new();
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let result = repl.eval("tree_encounter_count 3 1");
+        panic::set_hook(hook);
+        assert!(result.unwrap_err().starts_with("panicked: "));
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut repl = Repl::new(day(2021, 7), Config::new(2021, 7), repo_root());
+        repl.limit = 2;
+        assert_eq!(
+            repl.truncate("a\nb\nc\nd"),
+            "a\nb\n... 2 more lines, limit 0 shows everything"
+        );
+        repl.eval("limit 0").unwrap();
+        assert_eq!(repl.truncate("a\nb\nc\nd"), "a\nb\nc\nd");
+    }
+}
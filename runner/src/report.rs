@@ -0,0 +1,447 @@
+//! `aoc report`, a static HTML site with the answers and timings of all days.
+//!
+//! Everything is generated from the stored inputs and files in the repository,
+//! nothing is fetched. Images and cached puzzle descriptions are copied next to
+//! the pages, so the output directory can be shared as it is.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::{logging, run::PartResult, schedule, Answer, Config, Grid};
+
+use crate::{repo_root, run::run_day, select, take_flag, take_switch};
+
+const DEFAULT_OUT: &str = "site";
+/// Cached puzzle description, relative to the day directory
+const PUZZLE: &str = "data/puzzle.md";
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "svg"];
+
+const STYLE: &str = "body{background:#0f0f23;color:#ccc;font-family:monospace;margin:2em}
+a{color:#090;text-decoration:none}a:hover{color:#9f9}
+h1,h2{color:#0c0}h2{margin-top:2em}
+.calendar{display:grid;grid-template-columns:repeat(5,7em);gap:.5em}
+.calendar>*{border:1px solid #333;padding:.5em}.calendar span.missing{color:#444}
+.gold{color:#ff0}.silver{color:#99c}.none{color:#333}
+table{border-collapse:collapse}td,th{border:1px solid #333;padding:.2em .6em;text-align:left}
+td.time{text-align:right}.error{color:#f66}img{max-width:100%;margin-top:1em}";
+
+struct DayReport {
+    year: u16,
+    day: u8,
+    results: Vec<PartResult>,
+    /// Links relative to the site root
+    puzzle: Option<String>,
+    images: Vec<String>,
+}
+
+pub fn report(mut args: Vec<String>) -> Result<bool, String> {
+    let redact = take_switch(&mut args, "--redact");
+    let root = repo_root();
+    let out = take_flag(&mut args, "--out")?
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(DEFAULT_OUT));
+    let (selected, flags) = select(args)?;
+
+    let mut years = BTreeMap::<u16, Vec<DayReport>>::new();
+    let mut is_ok = true;
+
+    for solution in selected {
+        let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+            .map_err(|err| err.to_string())?;
+        logging::init(&config).map_err(|err| err.to_string())?;
+
+        let results = match run_day(solution, &config, &root, None)? {
+            Some(day) => day.results,
+            None => {
+                is_ok = false;
+                Vec::new()
+            }
+        };
+
+        let day_dir = config.day_dir(&root);
+        let asset_dir = format!("{}/{:02}", config.year, config.day);
+        let copy = |file: &Path| -> Result<String, String> {
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            let target = out.join(&asset_dir).join(name.as_ref());
+            fs::create_dir_all(out.join(&asset_dir))
+                .and_then(|_| fs::copy(file, &target))
+                .map_err(|err| format!("{}: {}", target.display(), err))?;
+            Ok(format!("{}/{}", asset_dir, encode(&name)))
+        };
+
+        let puzzle = day_dir.join(PUZZLE);
+        let puzzle = match puzzle.is_file() {
+            true => Some(copy(&puzzle)?),
+            false => None,
+        };
+        // screenshots usually show the answers
+        let images = match redact {
+            true => Vec::new(),
+            false => images(&day_dir)
+                .iter()
+                .map(|image| copy(image))
+                .collect::<Result<_, _>>()?,
+        };
+
+        years.entry(config.year).or_default().push(DayReport {
+            year: config.year,
+            day: config.day,
+            results,
+            puzzle,
+            images,
+        });
+    }
+
+    fs::create_dir_all(&out).map_err(|err| format!("{}: {}", out.display(), err))?;
+    let mut pages = vec![(out.join("index.html"), index_page(&years))];
+    for (year, days) in &years {
+        pages.push((
+            out.join(format!("{}.html", year)),
+            year_page(*year, days, redact),
+        ));
+    }
+    for (path, html) in pages {
+        fs::write(&path, html).map_err(|err| format!("{}: {}", path.display(), err))?;
+    }
+
+    println!("report written to {}", out.join("index.html").display());
+    Ok(is_ok)
+}
+
+/// Image files directly in the day directory, e.g. screenshots
+fn images(day_dir: &Path) -> Vec<PathBuf> {
+    let mut images = fs::read_dir(day_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| {
+                    path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    images.sort();
+    images
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Star {
+    /// Matches the stored answer
+    Gold,
+    /// Solved without a stored answer to compare with
+    Silver,
+    None,
+}
+
+impl Star {
+    fn of(result: &PartResult) -> Self {
+        match (&result.answer, result.is_correct()) {
+            (Ok(_), Some(true)) => Star::Gold,
+            (Ok(_), None) => Star::Silver,
+            _ => Star::None,
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Star::Gold => "gold",
+            Star::Silver => "silver",
+            Star::None => "none",
+        }
+    }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn index_page(years: &BTreeMap<u16, Vec<DayReport>>) -> String {
+    let mut body = String::from("<h1>Advent of Code</h1>\n<ul>\n");
+    for (year, days) in years {
+        let results = days.iter().flat_map(|day| &day.results);
+        let stars = results
+            .clone()
+            .filter(|result| Star::of(result) == Star::Gold)
+            .count();
+        let elapsed = results.map(|result| result.elapsed).sum::<Duration>();
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}.html\">{}</a>: {} days, <span class=\"gold\">{}*</span>, {:.2?}</li>",
+            year,
+            year,
+            days.len(),
+            stars,
+            elapsed
+        );
+    }
+    body += "</ul>\n";
+    page("Advent of Code", &body)
+}
+
+fn year_page(year: u16, days: &[DayReport], redact: bool) -> String {
+    let mut body = format!(
+        "<h1><a href=\"index.html\">Advent of Code</a> {}</h1>\n",
+        year
+    );
+
+    body += "<div class=\"calendar\">\n";
+    for day in 1..=schedule::days_in(year) {
+        match days.iter().find(|report| report.day == day) {
+            Some(report) => {
+                let stars = report
+                    .results
+                    .iter()
+                    .map(|result| format!("<span class=\"{}\">*</span>", Star::of(result).class()))
+                    .collect::<String>();
+                let _ = writeln!(body, "<a href=\"#day-{:02}\">{:>2} {}</a>", day, day, stars);
+            }
+            None => {
+                let _ = writeln!(body, "<span class=\"missing\">{:>2}</span>", day);
+            }
+        }
+    }
+    body += "</div>\n";
+
+    body += "<h2>Timings</h2>\n";
+    body += &timing_chart(days);
+
+    for report in days {
+        body += &day_section(report, redact);
+    }
+
+    page(&format!("Advent of Code {}", year), &body)
+}
+
+fn day_section(report: &DayReport, redact: bool) -> String {
+    let mut section = format!(
+        "<h2 id=\"day-{:02}\">Day {}</h2>\n<p><a href=\"https://adventofcode.com/{}/day/{}\">puzzle</a>",
+        report.day, report.day, report.year, report.day
+    );
+    if let Some(puzzle) = &report.puzzle {
+        let _ = write!(section, " | <a href=\"{}\">cached description</a>", puzzle);
+    }
+    section += "</p>\n";
+
+    if report.results.is_empty() {
+        section += "<p class=\"error\">input missing</p>\n";
+    } else {
+        section += "<table>\n<tr><th>part</th><th>answer</th><th>time</th></tr>\n";
+        for result in &report.results {
+            let answer = match &result.answer {
+                Ok(_) if redact => "<i>redacted</i>".to_string(),
+                Ok(Answer::Grid(grid)) => grid_svg(grid),
+                Ok(answer) => escape(&answer.to_string()),
+                Err(err) => format!("<span class=\"error\">{}</span>", escape(&err.to_string())),
+            };
+            let _ = writeln!(
+                section,
+                "<tr><td class=\"{}\">{}</td><td>{}</td><td class=\"time\">{:.2?}</td></tr>",
+                Star::of(result).class(),
+                result.part,
+                answer,
+                result.elapsed
+            );
+        }
+        section += "</table>\n";
+    }
+
+    for image in &report.images {
+        let _ = writeln!(
+            section,
+            "<img src=\"{}\" alt=\"{}/{:02}\" loading=\"lazy\">",
+            image, report.year, report.day
+        );
+    }
+    section
+}
+
+/// Horizontal bars on a logarithmic scale, the times span several magnitudes
+fn timing_chart(days: &[DayReport]) -> String {
+    const ROW: usize = 18;
+    const LABEL: usize = 110;
+    const WIDTH: f64 = 500.0;
+
+    let results = days.iter().flat_map(|day| &day.results).collect::<Vec<_>>();
+    // anything below a microsecond gets the minimal bar
+    let scale = |elapsed: Duration| (elapsed.as_nanos().max(1) as f64 / 1e3).log10().max(0.0);
+    let max = results
+        .iter()
+        .map(|result| scale(result.elapsed))
+        .fold(1.0, f64::max);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-size=\"12\">\n",
+        LABEL + WIDTH as usize + 100,
+        results.len() * ROW
+    );
+    for (idx, result) in results.iter().enumerate() {
+        let y = idx * ROW;
+        let width = 2.0 + scale(result.elapsed) / max * WIDTH;
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\" fill=\"#ccc\">{:02} part {}</text>\
+             <rect x=\"{}\" y=\"{}\" width=\"{:.0}\" height=\"{}\" fill=\"#090\"/>\
+             <text x=\"{:.0}\" y=\"{}\" fill=\"#ccc\">{:.2?}</text>",
+            y + 13,
+            result.day,
+            result.part,
+            LABEL,
+            y + 3,
+            width,
+            ROW - 6,
+            LABEL as f64 + width + 6.0,
+            y + 13,
+            result.elapsed
+        );
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+fn grid_svg(grid: &Grid) -> String {
+    const CELL: usize = 6;
+
+    let columns = grid
+        .rows()
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+        columns * CELL,
+        grid.rows().len() * CELL
+    );
+    for (y, row) in grid.rows().iter().enumerate() {
+        for (x, _) in row.chars().enumerate().filter(|(_, c)| !c.is_whitespace()) {
+            let _ = write!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#ff0\"/>",
+                x * CELL,
+                y * CELL,
+                CELL,
+                CELL
+            );
+        }
+    }
+    svg += "</svg>";
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&#39;",
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+/// Percent-encodes a file name for use in a link
+fn encode(name: &str) -> String {
+    name.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::SolveError;
+
+    use super::*;
+
+    fn result(part: u8, answer: Result<Answer, SolveError>, expected: Option<u32>) -> PartResult {
+        PartResult {
+            year: 2021,
+            day: 13,
+            part,
+            user: None,
+            answer,
+            elapsed: Duration::from_micros(1500),
+            expected: expected.map(Answer::from),
+            memory: None,
+        }
+    }
+
+    fn day() -> DayReport {
+        DayReport {
+            year: 2021,
+            day: 13,
+            results: vec![
+                result(1, Ok(Answer::from(710u32)), Some(710)),
+                result(2, Ok("X X\n X ".parse().unwrap()), None),
+            ],
+            puzzle: None,
+            images: vec!["2021/13/Screenshot%201.png".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(encode("Screenshot 1.png"), "Screenshot%201.png");
+    }
+
+    #[test]
+    fn test_year_page() {
+        let html = year_page(2021, &[day()], false);
+        assert!(html.contains("<a href=\"#day-13\">13 <span class=\"gold\">*</span><span class=\"silver\">*</span></a>"));
+        assert!(html.contains("<span class=\"missing\"> 1</span>"));
+        assert!(html.contains("<span class=\"missing\">25</span>"));
+        // the event is 12 days long from 2025 on
+        let short = year_page(2025, &[], false);
+        assert!(short.contains("<span class=\"missing\">12</span>"));
+        assert!(!short.contains("<span class=\"missing\">13</span>"));
+        assert!(html.contains("<td>710</td>"));
+        assert_eq!(html.matches("fill=\"#ff0\"").count(), 3);
+        assert!(html.contains("<img src=\"2021/13/Screenshot%201.png\""));
+        assert!(html.contains("1.50ms"));
+
+        let mut report = day();
+        report.images.clear();
+        let html = year_page(2021, &[report], true);
+        assert!(!html.contains(">710<"));
+        assert_eq!(html.matches("<i>redacted</i>").count(), 2);
+    }
+
+    #[test]
+    fn test_failed_part() {
+        let mut report = day();
+        report.results[0] = result(1, Ok(Answer::from(711u32)), Some(710));
+        report.results[1] = result(2, Err(SolveError::NoSolution("none".to_string())), None);
+        let html = day_section(&report, false);
+        assert!(html.contains("<td class=\"none\">1</td>"));
+        assert!(html.contains("<span class=\"error\">no solution: none</span>"));
+    }
+
+    #[test]
+    fn test_year_page_snapshot() {
+        aoc_common::assert_snapshot!("year_page", year_page(2021, &[day()], false));
+    }
+}
@@ -0,0 +1,235 @@
+//! `aoc run`, solving the stored inputs.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::{
+    answers::StoredAnswers,
+    config::Format,
+    crypto,
+    input::read_input,
+    logging,
+    run::{self, PartResult},
+    Abort, Config, Solution, SolveError,
+};
+use aoc_runner::solutions;
+
+use crate::{
+    isolate::{self, Isolation},
+    repo_root, select, take_flag, take_switch,
+};
+
+#[cfg(feature = "alloc-stats")]
+use crate::alloc::measure;
+
+#[cfg(not(feature = "alloc-stats"))]
+fn measure(run: impl FnOnce() -> PartResult) -> PartResult {
+    run()
+}
+
+pub fn run(mut args: Vec<String>) -> Result<bool, String> {
+    let record = take_switch(&mut args, "--record");
+    let isolate = take_switch(&mut args, "--isolate");
+    let all_inputs = take_switch(&mut args, "--all-inputs");
+    let child = take_switch(&mut args, isolate::CHILD);
+    let timeout = take_flag(&mut args, "--timeout")?
+        .map(|value| isolate::parse_duration(&value))
+        .transpose()?;
+    let memory_limit = take_flag(&mut args, "--memory-limit")?
+        .map(|value| isolate::parse_size(&value))
+        .transpose()?;
+    let (selected, flags) = select(args)?;
+
+    if timeout.is_some() && !isolate {
+        return Err("--timeout needs --isolate".to_string());
+    }
+    let isolation = match isolate {
+        true => Some(Isolation::new(&flags, timeout, memory_limit)?),
+        // without isolation the limit applies to this process
+        false => {
+            if let Some(memory_limit) = memory_limit {
+                isolate::limit_memory(memory_limit)?;
+            }
+            None
+        }
+    };
+    let root = repo_root();
+    let mut results: Vec<PartResult> = Vec::new();
+    let mut format = Format::Text;
+    let mut is_ok = true;
+
+    for solution in selected {
+        let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+            .map_err(|err| err.to_string())?;
+        // only the first day installs the subscriber
+        logging::init(&config).map_err(|err| err.to_string())?;
+        format = config.format;
+
+        if child {
+            return run_child(solution, &config, &root);
+        }
+
+        let configs = match all_inputs {
+            true => stored_inputs(&config, &root),
+            false => vec![config],
+        };
+
+        for config in configs {
+            let Some(mut day) = run_day(solution, &config, &root, isolation.as_ref())? else {
+                is_ok = false;
+                continue;
+            };
+
+            if record {
+                // keep answers of parts not run this time if the input is the same
+                let mut stored = day
+                    .stored
+                    .take()
+                    .filter(|stored| stored.is_for(&day.input))
+                    .unwrap_or_else(|| StoredAnswers::new(&day.input));
+                day.results
+                    .iter()
+                    .filter_map(|result| Some((result.part, result.answer.clone().ok()?)))
+                    .for_each(|(part, answer)| stored.set(part, answer));
+                stored
+                    .save(&day.answers_path)
+                    .map_err(|err| err.to_string())?;
+            }
+
+            day.results
+                .iter()
+                .for_each(|result| result.print(config.format));
+            results.extend(day.results);
+        }
+    }
+
+    is_ok &= results.iter().all(run::is_success);
+
+    if format == Format::Text && results.len() > 1 {
+        let total = results
+            .iter()
+            .map(|result| result.elapsed)
+            .sum::<Duration>();
+        let wrong = results
+            .iter()
+            // only answers can differ, aborted parts are counted on their own
+            .filter(|result| result.answer.is_ok() && result.is_correct() == Some(false))
+            .count();
+        println!(
+            "{} parts in {:?}, {} not matching the stored answers{}",
+            results.len(),
+            total,
+            wrong,
+            aborted_summary(&results)
+        );
+    }
+
+    Ok(is_ok)
+}
+
+pub(crate) struct DayRun {
+    pub input: String,
+    pub results: Vec<PartResult>,
+    pub stored: Option<StoredAnswers>,
+    pub answers_path: PathBuf,
+}
+
+/// The default input, if there is one, and the named inputs of all users
+fn stored_inputs(config: &Config, root: &Path) -> Vec<Config> {
+    let config = Config {
+        user: None,
+        ..config.clone()
+    };
+    let day_dir = config.day_dir(root);
+    let users = config.users(&day_dir);
+    let path = day_dir.join(config.input_path());
+
+    let mut configs = Vec::new();
+    if path.is_file() || crypto::encrypted_path(&path).is_file() || users.is_empty() {
+        configs.push(config.clone());
+    }
+    configs.extend(users.iter().map(|user| config.for_user(user)));
+    configs
+}
+
+/// Prints the results for the parent process, which checks and reports them
+fn run_child(solution: &dyn Solution, config: &Config, root: &Path) -> Result<bool, String> {
+    let path = config.day_dir(root).join(config.input_path());
+    let input = read_input(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    for &part in &config.parts {
+        let result = measure(|| run::run_part(solution, part, &input, config));
+        let line = serde_json::to_string(&result).map_err(|err| err.to_string())?;
+        println!("{}", line);
+    }
+    Ok(true)
+}
+
+/// Counts of the parts which didn't finish, by the way they ended
+fn aborted_summary(results: &[PartResult]) -> String {
+    let mut counts = [
+        ("timed out", 0),
+        ("panicked", 0),
+        ("out of memory", 0),
+        ("crashed", 0),
+    ];
+    for result in results {
+        let idx = match &result.answer {
+            Err(SolveError::Aborted(Abort::Timeout(_))) => 0,
+            Err(SolveError::Aborted(Abort::Panic(_))) => 1,
+            Err(SolveError::Aborted(Abort::OutOfMemory)) => 2,
+            Err(SolveError::Aborted(Abort::Crash(_))) => 3,
+            _ => continue,
+        };
+        counts[idx].1 += 1;
+    }
+
+    counts
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(status, count)| format!(", {} {}", count, status))
+        .collect()
+}
+
+/// Solves the stored input of one day and compares with the stored answers,
+/// `None` if the input can't be read. With `isolation` every part runs in a
+/// child process.
+pub(crate) fn run_day(
+    solution: &dyn Solution,
+    config: &Config,
+    root: &Path,
+    isolation: Option<&Isolation>,
+) -> Result<Option<DayRun>, String> {
+    let day_dir = config.day_dir(root);
+    let path = day_dir.join(config.input_path());
+    let input = match read_input(&path) {
+        Ok(input) => input,
+        // the parts report that there is no solver
+        Err(_) if !solutions::is_implemented(config.year, config.day) => String::new(),
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), err);
+            return Ok(None);
+        }
+    };
+
+    run::sniff_input(solution, &input, solutions::all());
+    let mut results = run::run_day_with(config, |part| match isolation {
+        Some(isolation) => isolation.run_part(solution, part, config.user.as_deref()),
+        None => measure(|| run::run_part(solution, part, &input, config)),
+    });
+
+    let answers_path = day_dir.join(config.answers_path());
+    let stored = StoredAnswers::load(&answers_path).map_err(|err| err.to_string())?;
+    if let Some(stored) = &stored {
+        run::verify(&mut results, stored, &input);
+    }
+
+    Ok(Some(DayRun {
+        input,
+        results,
+        stored,
+        answers_path,
+    }))
+}
@@ -0,0 +1,473 @@
+//! `aoc serve`, a small JSON HTTP API for tools that can't link the solvers.
+//!
+//! - `GET /days` lists the days with a solver, every solver has parts 1 and 2
+//! - `POST /solve/<year>/<day>/<part>` solves the input in the request body
+//!
+//! Answers have the same JSON shape as `aoc run --format json`, failures are
+//! `{"error": {"kind": ..., "message": ...}}` with a matching status code, a
+//! panicking solver is a 500 of kind `panic`. The server speaks just enough
+//! HTTP/1.1 for that, one request per connection, and answers 408 when a
+//! request doesn't arrive in time.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{run, Abort, Config, SolveError};
+use aoc_runner::solutions;
+use serde_json::{json, Value};
+
+use crate::{isolate, take_flag};
+
+const USAGE: &str = "usage: aoc serve [--port <port>] [--host <addr>] [--read-timeout <duration>]";
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 8025;
+
+// inputs are a few KiB, anything much larger is a mistake
+const MAX_BODY: usize = 16 << 20;
+/// Request line and headers together
+const MAX_HEAD: u64 = 8 << 10;
+/// For the whole request, a client sending slowly or less than it announced
+/// doesn't hold a thread longer
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// The parts of every solver
+const PARTS: [u8; 2] = [1, 2];
+
+pub fn serve(mut args: Vec<String>) -> Result<bool, String> {
+    let port = take_flag(&mut args, "--port")?
+        .map(|port| {
+            port.parse::<u16>()
+                .map_err(|_| format!("invalid port '{}'", port))
+        })
+        .transpose()?
+        .unwrap_or(DEFAULT_PORT);
+    let host = take_flag(&mut args, "--host")?.unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let read_timeout = take_flag(&mut args, "--read-timeout")?
+        .map(|timeout| isolate::parse_duration(&timeout))
+        .transpose()?
+        .unwrap_or(READ_TIMEOUT);
+    if !args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let server = Server::bind(&format!("{}:{}", host, port))?.read_timeout(read_timeout);
+    println!("serving on http://{}, Ctrl-C stops", server.local_addr());
+    server.run();
+    Ok(true)
+}
+
+pub struct Server {
+    listener: TcpListener,
+    read_timeout: Duration,
+}
+
+impl Server {
+    /// Port 0 picks a free port, see [`Server::local_addr`]
+    pub fn bind(addr: &str) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr).map_err(|err| format!("{}: {}", addr, err))?;
+        Ok(Self {
+            listener,
+            read_timeout: READ_TIMEOUT,
+        })
+    }
+
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.listener.local_addr().unwrap()
+    }
+
+    /// Answers requests until the process ends, every connection in its own
+    /// thread so a slow solver doesn't block the others
+    pub fn run(self) {
+        for stream in self.listener.incoming().flatten() {
+            let read_timeout = self.read_timeout;
+            thread::spawn(move || {
+                if let Err(err) = connection(stream, read_timeout) {
+                    eprintln!("connection: {}", err);
+                }
+            });
+        }
+    }
+}
+
+fn connection(mut stream: TcpStream, read_timeout: Duration) -> io::Result<()> {
+    let deadline = Deadline {
+        stream: &stream,
+        at: Instant::now() + read_timeout,
+    };
+    let (status, body) = match read_request(deadline) {
+        Ok(Ok((method, path, body))) => handle(&method, &path, &body),
+        Ok(Err((status, msg))) => error(status, "bad_request", msg),
+        // `WouldBlock` on Unix, `TimedOut` on Windows
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            error(
+                408,
+                "timeout",
+                format!("no complete request within {:?}", read_timeout),
+            )
+        }
+        Err(err) => return Err(err),
+    };
+
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Reads from `stream` until the deadline, the read timeout of the socket
+/// only limits a single read
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    at: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.at.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+type Request = (String, String, Vec<u8>);
+
+/// Method, path and body, or the status and message for a malformed request
+fn read_request(stream: Deadline) -> io::Result<Result<Request, (u16, String)>> {
+    let mut reader = BufReader::new(stream);
+    let mut head = (&mut reader).take(MAX_HEAD);
+    let too_large = || Ok(Err((431, format!("request head over {} bytes", MAX_HEAD))));
+
+    let mut line = String::new();
+    head.read_line(&mut line)?;
+    if head.limit() == 0 {
+        return too_large();
+    }
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Ok(Err((400, "malformed request line".to_string())));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if head.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if head.limit() == 0 {
+            return too_large();
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse::<usize>() {
+                    Ok(length) => content_length = length,
+                    Err(_) => return Ok(Err((400, "invalid Content-Length".to_string()))),
+                }
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return Ok(Err((
+            413,
+            format!("inputs are limited to {} bytes", MAX_BODY),
+        )));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok((method, path, body)))
+}
+
+/// Status and JSON body for a request
+fn handle(method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    match (method, &segments[..]) {
+        ("GET", ["days"]) => (200, days()),
+        ("POST", ["solve", year, day, part]) => solve(year, day, part, body),
+        (_, ["days"]) | (_, ["solve", _, _, _]) => error(
+            405,
+            "method_not_allowed",
+            format!("{} is not supported for {}", method, path),
+        ),
+        _ => error(
+            404,
+            "not_found",
+            format!(
+                "no route {}, try GET /days or POST /solve/<year>/<day>/<part>",
+                path
+            ),
+        ),
+    }
+}
+
+fn days() -> Value {
+    let days = solutions::all()
+        .iter()
+        .map(|solution| {
+            json!({
+                "year": solution.year(),
+                "day": solution.day(),
+                "parts": PARTS,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "days": days })
+}
+
+fn solve(year: &str, day: &str, part: &str, body: &[u8]) -> (u16, Value) {
+    let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+        return error(404, "not_found", format!("no puzzle {}/{}", year, day));
+    };
+    let Some(solution) = solutions::all()
+        .iter()
+        .find(|solution| solution.year() == year && solution.day() == day)
+    else {
+        return error(
+            404,
+            "not_found",
+            format!("no solver for {}/{:02}", year, day),
+        );
+    };
+    let part = match part.parse::<u8>() {
+        Ok(part) if PARTS.contains(&part) => part,
+        _ => return error(400, "invalid_part", format!("invalid part '{}'", part)),
+    };
+    let Ok(input) = String::from_utf8(body.to_vec()) else {
+        return error(400, "bad_request", "the input isn't UTF-8".to_string());
+    };
+
+    // the repository configuration, e.g. per-day parameters
+    let config = match Config::load_from(year, day, Vec::new()) {
+        Ok(config) => config,
+        Err(err) => return error(500, "config", err.to_string()),
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run::run_part(*solution, part, &input, &config)
+    }));
+
+    match result {
+        Ok(result) => match &result.answer {
+            Ok(_) => (200, result.to_json()),
+            Err(err) => solve_error(err),
+        },
+        Err(payload) => solve_error(&SolveError::Aborted(Abort::Panic(run::panic_message(
+            &*payload,
+        )))),
+    }
+}
+
+fn solve_error(err: &SolveError) -> (u16, Value) {
+    let (status, kind) = match err {
+        SolveError::Parse(_) => (422, "parse"),
+        SolveError::NoSolution(_) => (422, "no_solution"),
+        SolveError::InvalidPart(_) => (400, "invalid_part"),
+        SolveError::NotImplemented => (501, "not_implemented"),
+        SolveError::Aborted(Abort::Panic(_)) => (500, "panic"),
+        SolveError::Aborted(_) => (500, "aborted"),
+    };
+    error(status, kind, err.to_string())
+}
+
+fn error(status: u16, kind: &str, message: String) -> (u16, Value) {
+    (
+        status,
+        json!({ "error": { "kind": kind, "message": message } }),
+    )
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        422 => "Unprocessable Entity",
+        501 => "Not Implemented",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Status and body of one request to the test server
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr();
+        thread::spawn(move || server.run());
+
+        let (status, body) = request(addr, "GET", "/days", "");
+        assert_eq!(status, 200);
+        assert!(body["days"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "year": 2021, "day": 7, "parts": [1, 2] })));
+
+        let (status, body) = request(addr, "POST", "/solve/2021/7/1", "16,1,2,0,4,2,7,1,2,14");
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], 37);
+        assert!(body["elapsed_ns"].is_u64());
+
+        let (status, body) = request(addr, "POST", "/solve/2021/07/2", "16,1,2,0,4,2,7,1,2,14\n");
+        assert_eq!((status, &body["answer"]), (200, &json!(168)));
+
+        let (status, body) = request(addr, "POST", "/solve/2021/13/1", "6,10\n0,14\n");
+        assert_eq!(status, 422);
+        assert_eq!(
+            body["error"],
+            json!({ "kind": "parse", "message": "invalid input: no fold instructions" })
+        );
+
+        let (status, body) = request(addr, "POST", "/solve/2021/7/3", "1");
+        assert_eq!(status, 400);
+        assert_eq!(body["error"]["kind"], "invalid_part");
+
+        let (status, body) = request(addr, "POST", "/solve/2019/1/1", "1");
+        assert_eq!(status, 404);
+        assert_eq!(body["error"]["kind"], "not_found");
+
+        // 2020/03 panics on an empty map
+        let (status, body) = request(addr, "POST", "/solve/2020/3/1", "");
+        assert_eq!(status, 500);
+        assert_eq!(body["error"]["kind"], "panic");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("panicked: "));
+
+        let (status, _) = request(addr, "GET", "/solve/2021/7/1", "");
+        assert_eq!(status, 405);
+        let (status, _) = request(addr, "GET", "/", "");
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn test_read_timeout() {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .read_timeout(Duration::from_millis(200));
+        let addr = server.local_addr();
+        thread::spawn(move || server.run());
+
+        // announces more than it sends
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /solve/2021/7/1 HTTP/1.1\r\nContent-Length: 100\r\n\r\n16,1"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+        assert!(response.contains(r#""kind":"timeout""#));
+    }
+
+    #[test]
+    fn test_slow_request() {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .read_timeout(Duration::from_millis(300));
+        let addr = server.local_addr();
+        thread::spawn(move || server.run());
+
+        // every byte within the timeout, the whole request not
+        let start = Instant::now();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        for byte in b"GET /days HTTP/1.1\r\n" {
+            if stream.write_all(&[*byte]).is_err() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_large_head() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr();
+        thread::spawn(move || server.run());
+
+        // a head of exactly the limit without its end, the server reads all
+        // of it and closes without resetting the connection
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut head = "GET /days HTTP/1.1\r\nCookie: ".to_string();
+        head += &"x".repeat(MAX_HEAD as usize - head.len());
+        stream.write_all(head.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(
+            solve_error(&SolveError::Parse("line 3".to_string())),
+            error(422, "parse", "invalid input: line 3".to_string())
+        );
+        assert_eq!(solve_error(&SolveError::NotImplemented).0, 501);
+        assert_eq!(
+            handle("POST", "/solve/2021/x/1", b"")
+                .1
+                .pointer("/error/kind")
+                .unwrap(),
+            "not_found"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Days known to the runner, generated by `build.rs` from the `<year>/<day>`
+//! directories, and the plugins loaded at runtime.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use aoc_common::{
+    plugin::{self, Plugin},
+    Answer, Config, Solution, SolveError,
+};
+
+use crate::repo_root;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+pub const PLUGIN_DIR: &str = "plugins";
+pub const PLUGIN_DIR_VAR: &str = "AOC_PLUGIN_DIR";
+
+/// The built-in solvers and the plugins of `$AOC_PLUGIN_DIR` or `plugins/` of
+/// the repository, sorted by day
+pub fn all() -> &'static [&'static dyn Solution] {
+    static ALL: OnceLock<Vec<&'static dyn Solution>> = OnceLock::new();
+    ALL.get_or_init(|| {
+        let dir = env::var_os(PLUGIN_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| repo_root().join(PLUGIN_DIR));
+        with_plugins(&dir)
+    })
+}
+
+/// A plugin replaces the built-in solver of its day, plugins that fail to
+/// load are reported and skipped
+fn with_plugins(dir: &Path) -> Vec<&'static dyn Solution> {
+    let mut all = SOLUTIONS.to_vec();
+    for (path, plugin) in plugin::discover(dir) {
+        let plugin = match plugin {
+            // loaded for the rest of the process
+            Ok(plugin) => &*Box::leak(Box::new(plugin)),
+            Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                continue;
+            }
+        };
+        let is_day = |solution: &&dyn Solution| {
+            solution.year() == plugin.year() && solution.day() == plugin.day()
+        };
+        if all.iter().any(is_day) {
+            eprintln!(
+                "{}/{:02}: using the plugin {}",
+                plugin.year(),
+                plugin.day(),
+                plugin.path().display()
+            );
+        }
+        all.retain(|solution| !is_day(solution));
+        all.push(plugin as &Plugin);
+    }
+    all.sort_by_key(|solution| (solution.year(), solution.day()));
+    all
+}
+
+pub fn is_implemented(year: u16, day: u8) -> bool {
+    all()
+        .iter()
+        .any(|solution| solution.year() == year && solution.day() == day)
+}
+
+/// Stands in for a puzzle day without a solver
+pub struct NotImplemented {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Solution for NotImplemented {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn part1(&self, _input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(&self, _input: &str, _config: &Config) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::consts::DLL_EXTENSION, fs, process};
+
+    use super::*;
+
+    #[test]
+    fn test_with_plugins() {
+        let dir = env::temp_dir().join(format!("aoc-runner-plugins-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("broken.{}", DLL_EXTENSION)), b"").unwrap();
+        let all = with_plugins(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        // the broken plugin is skipped
+        assert_eq!(all.len(), SOLUTIONS.len());
+        assert!(all
+            .windows(2)
+            .all(|pair| (pair[0].year(), pair[0].day()) < (pair[1].year(), pair[1].day())));
+    }
+}
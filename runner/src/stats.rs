@@ -0,0 +1,193 @@
+//! Welch's t-test for comparing benchmark samples, and Holm's correction for
+//! comparing many at once.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Welch {
+    pub t: f64,
+    pub df: f64,
+    /// Two-sided p-value
+    pub p: f64,
+}
+
+pub fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// Sample variance
+pub fn variance(samples: &[f64]) -> f64 {
+    let mean = mean(samples);
+    samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (samples.len() - 1) as f64
+}
+
+/// `None` with less than two samples on either side
+pub fn welch(a: &[f64], b: &[f64]) -> Option<Welch> {
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let (va, vb) = (variance(a) / na, variance(b) / nb);
+    let diff = mean(b) - mean(a);
+
+    // identical timings, e.g. from a coarse clock
+    if va + vb == 0.0 {
+        let p = if diff == 0.0 { 1.0 } else { 0.0 };
+        return Some(Welch {
+            t: diff.signum() * f64::INFINITY,
+            df: na + nb - 2.0,
+            p,
+        });
+    }
+
+    let t = diff / (va + vb).sqrt();
+    let df = (va + vb).powi(2) / (va.powi(2) / (na - 1.0) + vb.powi(2) / (nb - 1.0));
+    let p = incomplete_beta(df / 2.0, 0.5, df / (df + t * t));
+    Some(Welch { t, df, p })
+}
+
+/// Holm-Bonferroni adjusted p-values, in the order of `p`. Comparing them
+/// with the significance level keeps the chance of any false positive below
+/// it.
+pub fn holm(p: &[f64]) -> Vec<f64> {
+    let mut order = (0..p.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| p[i].total_cmp(&p[j]));
+
+    let mut adjusted = vec![0.0; p.len()];
+    let mut max = 0.0_f64;
+    for (rank, &idx) in order.iter().enumerate() {
+        // the adjusted values must not drop below a smaller p's
+        max = max.max(((p.len() - rank) as f64 * p[idx]).min(1.0));
+        adjusted[idx] = max;
+    }
+    adjusted
+}
+
+// Lanczos approximation, g = 7
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    // the continued fraction converges quickly below the mean only
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// Lentz's method for the continued fraction of the incomplete beta function
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    const EPSILON: f64 = 1e-14;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    d = 1.0 / if d.abs() < TINY { TINY } else { d };
+    let mut h = d;
+
+    for m in 1..300 {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            d = 1.0 / if d.abs() < TINY { TINY } else { d };
+            c = 1.0 + numerator / c;
+            c = if c.abs() < TINY { TINY } else { c };
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_ln_gamma() {
+        assert_close(ln_gamma(1.0), 0.0);
+        assert_close(ln_gamma(5.0), 24f64.ln());
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln());
+    }
+
+    #[test]
+    fn test_welch() {
+        // two-sided critical values of the t-distribution
+        assert_close(
+            incomplete_beta(5.0, 0.5, 10.0 / (10.0 + 2.228_f64.powi(2))),
+            0.05,
+        );
+        assert_close(
+            incomplete_beta(2.5, 0.5, 5.0 / (5.0 + 4.032_f64.powi(2))),
+            0.01,
+        );
+
+        let a = [10.0, 11.0, 9.0, 10.5, 9.5];
+        let same = welch(&a, &[10.2, 9.8, 10.1, 9.9, 10.0]).unwrap();
+        assert!(same.p > 0.5);
+
+        let slower = welch(&a, &[12.0, 13.0, 12.5, 11.5, 12.2]).unwrap();
+        assert!(slower.t > 0.0);
+        assert!(slower.p < 0.01);
+
+        assert_eq!(welch(&a, &[1.0]), None);
+        assert_eq!(welch(&[1.0, 1.0], &[1.0, 1.0]).unwrap().p, 1.0);
+    }
+
+    #[test]
+    fn test_holm() {
+        let adjusted = holm(&[0.04, 0.01, 0.03, 0.5]);
+        for (p, expected) in adjusted.iter().zip([0.09, 0.04, 0.09, 0.5]) {
+            assert_close(*p, expected);
+        }
+        assert_eq!(holm(&[0.6, 0.6]), vec![1.0, 1.0]);
+        assert!(holm(&[]).is_empty());
+    }
+}
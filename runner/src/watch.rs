@@ -0,0 +1,317 @@
+//! `aoc watch`, rebuilding and re-running one day whenever it changes.
+//!
+//! The day's `src/` and `data/` are watched with filesystem notifications.
+//! Every change rebuilds the day crate, runs its example tests and then the
+//! day binary on the real input, summarized in one pass/fail line.
+
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::mpsc,
+    time::Duration,
+};
+
+use aoc_common::{Config, Solution};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::{repo_root, select};
+
+const USAGE: &str = "usage: aoc watch <year> <day> [options]";
+
+// editors write a file in several steps, they are handled as one change
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Tests on the real input, by the convention of the day crates, the input
+/// is solved by the day binary instead
+const REAL_INPUT_TESTS: &[&str] = &["test_get_input", "test_solution"];
+
+// lines of compiler errors shown for a failed build
+const MAX_ERROR_LINES: usize = 10;
+
+pub fn watch(args: Vec<String>) -> Result<bool, String> {
+    let is_day = args.len() >= 2 && args[..2].iter().all(|arg| arg.parse::<u16>().is_ok());
+    let (selected, flags) = select(args)?;
+    let solution = match selected[..] {
+        [solution] if is_day => solution,
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let config = Config::load_from(solution.year(), solution.day(), flags.clone())
+        .map_err(|err| err.to_string())?;
+    let day_dir = config.day_dir(&repo_root());
+    if !day_dir.join("Cargo.toml").is_file() {
+        return Err(format!("{} has no crate yet", config.day_key()));
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|err| err.to_string())?;
+    for dir in ["src", "data"].map(|dir| day_dir.join(dir)) {
+        if dir.is_dir() {
+            watcher
+                .watch(&dir, RecursiveMode::Recursive)
+                .map_err(|err| format!("{}: {}", dir.display(), err))?;
+        }
+    }
+    println!("watching {} src/ and data/, Ctrl-C stops", config.day_key());
+
+    let package = format!("aoc-{}-{:02}", solution.year(), solution.day());
+    println!("{}", check(solution, &package, &day_dir, &flags));
+
+    while let Ok(event) = rx.recv() {
+        let mut changed = changes(event);
+        // collect the rest of the burst
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            changed.extend(changes(event));
+        }
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            continue;
+        }
+
+        // temporary files already renamed or removed again aren't interesting,
+        // unless nothing else changed
+        if changed.iter().any(|path| path.exists()) {
+            changed.retain(|path| path.exists());
+        }
+        let names = changed
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&day_dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        println!("\n{} changed", names.join(", "));
+        println!("{}", check(solution, &package, &day_dir, &flags));
+    }
+
+    Err("the file watcher stopped".to_string())
+}
+
+/// Modified files of `event`, without editor swap and backup files
+fn changes(event: notify::Result<Event>) -> Vec<PathBuf> {
+    let Ok(event) = event else {
+        return Vec::new();
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return Vec::new();
+    }
+    event
+        .paths
+        .into_iter()
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            !name.starts_with('.') && !name.ends_with('~') && !name.ends_with(".swp")
+        })
+        .collect()
+}
+
+fn cargo(day_dir: &Path, args: &[&str]) -> Result<Output, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    // the day binary finds its input relative to the day directory
+    Command::new(cargo)
+        .args(args)
+        .current_dir(day_dir)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("cargo: {}", err))
+}
+
+/// Builds, tests and runs the day, the summary starts with PASS or FAIL
+fn check(solution: &dyn Solution, package: &str, day_dir: &Path, flags: &[String]) -> String {
+    let label = format!("{}/{:02}", solution.year(), solution.day());
+    match run_steps(package, day_dir, flags) {
+        Ok((true, summary)) => format!("PASS {} {}", label, summary),
+        Ok((false, summary)) => format!("FAIL {} {}", label, summary),
+        Err(err) => format!("FAIL {} {}", label, err),
+    }
+}
+
+fn run_steps(package: &str, day_dir: &Path, flags: &[String]) -> Result<(bool, String), String> {
+    let build = cargo(
+        day_dir,
+        &[
+            "build",
+            "-q",
+            "--message-format",
+            "short",
+            "-p",
+            package,
+            "--all-targets",
+        ],
+    )?;
+    if !build.status.success() {
+        return Ok((
+            false,
+            format!(
+                "build failed\n{}",
+                build_errors(&String::from_utf8_lossy(&build.stderr))
+            ),
+        ));
+    }
+
+    let mut test_args = vec!["test", "-q", "-p", package, "--lib", "--"];
+    for test in REAL_INPUT_TESTS {
+        test_args.extend(["--skip", test]);
+    }
+    let tests = cargo(day_dir, &test_args)?;
+    let tests = test_summary(&String::from_utf8_lossy(&tests.stdout));
+
+    let mut run_args = vec!["run", "-q", "-p", package, "--"];
+    run_args.extend(flags.iter().map(String::as_str));
+    run_args.extend(["--format", "json"]);
+    let run = cargo(day_dir, &run_args)?;
+    let (answers_ok, answers) = answers_summary(&String::from_utf8_lossy(&run.stdout));
+    let answers = match answers.is_empty() {
+        true => {
+            // e.g. a missing input, the binary explains it on stderr
+            let stderr = String::from_utf8_lossy(&run.stderr);
+            format!("no answers: {}", stderr.lines().last().unwrap_or_default())
+        }
+        false => answers,
+    };
+
+    Ok((
+        tests.failed.is_empty() && answers_ok && run.status.success(),
+        format!("{}, {}", tests, answers),
+    ))
+}
+
+/// The first compiler errors of `cargo build --message-format short`
+fn build_errors(stderr: &str) -> String {
+    stderr
+        .lines()
+        .filter(|line| line.contains("error"))
+        .take(MAX_ERROR_LINES)
+        .map(|line| format!("  {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct TestSummary {
+    passed: usize,
+    failed: Vec<String>,
+}
+
+impl fmt::Display for TestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.failed.is_empty() {
+            true => write!(f, "{} example tests passed", self.passed),
+            false => write!(
+                f,
+                "{} of {} example tests failed ({})",
+                self.failed.len(),
+                self.passed + self.failed.len(),
+                self.failed.join(", ")
+            ),
+        }
+    }
+}
+
+/// Counts of the libtest output and the names of the failed tests
+fn test_summary(stdout: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+    for line in stdout.lines() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            let name = name.rsplit("::").next().unwrap_or(name);
+            summary.failed.push(name.to_string());
+        }
+        if let Some(counts) = line.strip_prefix("test result: ") {
+            // "ok. 5 passed; 0 failed; 0 ignored; ..."
+            summary.passed += counts
+                .split([';', '.'])
+                .find_map(|count| count.trim().strip_suffix(" passed")?.parse::<usize>().ok())
+                .unwrap_or(0);
+        }
+    }
+    summary
+}
+
+/// One `part N: answer` per JSON result line of the day binary, false if a
+/// part failed or differs from the stored answer
+fn answers_summary(stdout: &str) -> (bool, String) {
+    let mut is_ok = true;
+    let parts = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .map(|result| {
+            let part = &result["part"];
+            let answer = match (&result["answer"], &result["error"]) {
+                // grids span several lines
+                (serde_json::Value::String(grid), _) if grid.contains('\n') => "grid".to_string(),
+                (serde_json::Value::String(answer), _) => answer.clone(),
+                (serde_json::Value::Null, error) => {
+                    is_ok = false;
+                    format!("error: {}", error.as_str().unwrap_or_default())
+                }
+                (answer, _) => answer.to_string(),
+            };
+            match result["correct"].as_bool() {
+                Some(false) => {
+                    is_ok = false;
+                    format!("part {}: {} expected {}", part, answer, result["expected"])
+                }
+                _ => format!("part {}: {}", part, answer),
+            }
+        })
+        .collect::<Vec<_>>();
+    (is_ok, parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_summary() {
+        let stdout = "\nrunning 4 tests\n.F..\nfailures:\n\n\
+                      ---- tests::test_birth stdout ----\nassertion failed\n\n\
+                      test result: FAILED. 3 passed; 1 failed; 0 ignored; 0 measured; \
+                      2 filtered out; finished in 0.00s\n";
+        assert_eq!(
+            test_summary(stdout),
+            TestSummary {
+                passed: 3,
+                failed: vec!["test_birth".to_string()],
+            }
+        );
+        assert_eq!(
+            test_summary(stdout).to_string(),
+            "1 of 4 example tests failed (test_birth)"
+        );
+
+        let stdout = "test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; \
+                      2 filtered out; finished in 0.00s";
+        assert_eq!(test_summary(stdout).to_string(), "5 example tests passed");
+    }
+
+    #[test]
+    fn test_answers_summary() {
+        let stdout = r#"{"part":1,"answer":360268,"error":null,"correct":true,"expected":360268}
+{"part":2,"answer":17,"error":null,"correct":false,"expected":42}"#;
+        assert_eq!(
+            answers_summary(stdout),
+            (false, "part 1: 360268, part 2: 17 expected 42".to_string())
+        );
+
+        let stdout = r#"{"part":1,"answer":"ABC","error":null,"correct":null,"expected":null}
+{"part":2,"answer":null,"error":"not implemented","correct":null,"expected":null}"#;
+        assert_eq!(
+            answers_summary(stdout),
+            (
+                false,
+                "part 1: ABC, part 2: error: not implemented".to_string()
+            )
+        );
+    }
+}